@@ -0,0 +1,227 @@
+use crate::bitset::SmallBitSet;
+use crate::component::CellFlags;
+use crate::rng::SplitMix64;
+
+/// The parts of a cell a behavior may inspect or update while the cell is processed
+pub struct CellContext<'a> {
+  pub flags: &'a mut CellFlags,
+  pub signals: &'a SmallBitSet,
+  pub last_fired_cycle: &'a mut Option<usize>,
+  // outgoing signal bit a router forwards on, see `Router`
+  pub selected_output: &'a mut Option<u8>,
+  // sum of the weights an accumulator received, see `Accumulator`
+  pub accumulated: &'a mut i8,
+  // the instance's random number generator
+  pub rng: &'a mut SplitMix64,
+  pub instance_cycle: usize,
+}
+
+impl<'a> CellContext<'a> {
+  pub fn fire(&mut self) {
+    self.flags.insert(CellFlags::FIRED);
+  }
+}
+
+/// What happens to a cell's signals once it has been processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalReset {
+  /// Signals only count for the step they arrived in
+  ClearAll,
+  /// Signals are kept until the instance's state is cleared, e.g. for sequence detection
+  Retain,
+  /// Signals are kept while the cell waits and cleared once it fires
+  ClearMatched,
+}
+
+/// What a cell does when it is processed. Each `CellType` maps to one behavior.
+pub trait CellBehavior {
+  fn on_process(&mut self, ctx: &mut CellContext);
+
+  fn signal_reset(&self) -> SignalReset {
+    SignalReset::ClearAll
+  }
+}
+
+/// Fires every time it is processed
+pub struct Relay;
+
+impl CellBehavior for Relay {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    ctx.fire();
+  }
+}
+
+/// Fires when processed. Its cell is expected to be staged only once.
+pub struct OneShot;
+
+impl CellBehavior for OneShot {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    ctx.fire();
+  }
+}
+
+/// Fires when processed. Sensors are staged through Association or Observe edges and
+/// are allowed to be terminal, unlike relays.
+pub struct Sensor;
+
+impl CellBehavior for Sensor {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    ctx.fire();
+  }
+}
+
+/// Demultiplexer. The lowest signal bit it receives selects the output, and it only
+/// forwards on the outgoing Signal edges with that bit.
+pub struct Router;
+
+impl CellBehavior for Router {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    if let Some(bit) = ctx.signals.first() {
+      *ctx.selected_output = Some(bit as u8);
+      ctx.fire();
+    }
+  }
+}
+
+/// Fires once the weights accumulated from incoming signals reach `threshold`, which
+/// resets the sum
+pub struct Accumulator {
+  pub threshold: i8,
+}
+
+impl CellBehavior for Accumulator {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    if *ctx.accumulated >= self.threshold {
+      *ctx.accumulated = 0;
+      ctx.fire();
+    }
+  }
+
+  // the bits received so far tell which inputs contributed to the sum
+  fn signal_reset(&self) -> SignalReset {
+    SignalReset::ClearMatched
+  }
+}
+
+/// Fires once signals on bits 0..`inputs` have all arrived, over as many cycles as it
+/// takes, and from then on every time it is processed
+pub struct Latch {
+  pub inputs: u8,
+}
+
+impl CellBehavior for Latch {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    if (0..self.inputs as usize).all(|bit| ctx.signals.contains(bit)) {
+      ctx.fire();
+    }
+  }
+
+  // the bits received so far are the part of the sequence already seen
+  fn signal_reset(&self) -> SignalReset {
+    SignalReset::Retain
+  }
+}
+
+/// Fires with probability `probability`
+pub struct RandomGate {
+  pub probability: f32,
+}
+
+impl CellBehavior for RandomGate {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    if ctx.rng.next_f32() < self.probability {
+      ctx.fire();
+    }
+  }
+}
+
+/// Fires, then ignores processing until `cooldown` instance cycles have passed
+pub struct OneShotRearm {
+  pub cooldown: usize,
+}
+
+impl CellBehavior for OneShotRearm {
+  fn on_process(&mut self, ctx: &mut CellContext) {
+    let armed = match *ctx.last_fired_cycle {
+      Some(last_fired_cycle) => ctx.instance_cycle > last_fired_cycle + self.cooldown,
+      None => true,
+    };
+    if armed {
+      ctx.fire();
+      *ctx.last_fired_cycle = Some(ctx.instance_cycle);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn process(behavior: &mut dyn CellBehavior, instance_cycle: usize) -> (CellFlags, Option<usize>) {
+    let mut flags = CellFlags::empty();
+    let mut last_fired_cycle = None;
+    behavior.on_process(&mut CellContext {
+      flags: &mut flags,
+      signals: &SmallBitSet::new(),
+      last_fired_cycle: &mut last_fired_cycle,
+      selected_output: &mut None,
+      accumulated: &mut 0,
+      rng: &mut SplitMix64::new(0),
+      instance_cycle,
+    });
+    (flags, last_fired_cycle)
+  }
+
+  #[test]
+  fn relay_fires() {
+    let (flags, last_fired_cycle) = process(&mut Relay, 7);
+    assert!(flags.contains(CellFlags::FIRED));
+    assert_eq!(last_fired_cycle, None);
+  }
+
+  #[test]
+  fn one_shot_fires() {
+    let (flags, _) = process(&mut OneShot, 0);
+    assert!(flags.contains(CellFlags::FIRED));
+  }
+
+  #[test]
+  fn latch_waits_for_every_input() {
+    let mut signals = SmallBitSet::new();
+    for (bit, fires) in [(1, false), (0, true)] {
+      signals.insert(bit);
+      let mut flags = CellFlags::empty();
+      Latch { inputs: 2 }.on_process(&mut CellContext {
+        flags: &mut flags,
+        signals: &signals,
+        last_fired_cycle: &mut None,
+        selected_output: &mut None,
+        accumulated: &mut 0,
+        rng: &mut SplitMix64::new(0),
+        instance_cycle: 0,
+      });
+      assert_eq!(flags.contains(CellFlags::FIRED), fires);
+    }
+  }
+
+  #[test]
+  fn one_shot_rearm_waits_for_cooldown() {
+    let mut behavior = OneShotRearm { cooldown: 2 };
+    let mut flags = CellFlags::empty();
+    let mut last_fired_cycle = Some(3);
+    for (instance_cycle, fires) in [(4, false), (5, false), (6, true)] {
+      flags.remove(CellFlags::FIRED);
+      behavior.on_process(&mut CellContext {
+        flags: &mut flags,
+        signals: &SmallBitSet::new(),
+        last_fired_cycle: &mut last_fired_cycle,
+        selected_output: &mut None,
+        accumulated: &mut 0,
+        rng: &mut SplitMix64::new(0),
+        instance_cycle,
+      });
+      assert_eq!(flags.contains(CellFlags::FIRED), fires);
+    }
+    assert_eq!(last_fired_cycle, Some(6));
+  }
+}
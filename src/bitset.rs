@@ -0,0 +1,142 @@
+use std::hash::{Hash, Hasher};
+
+/// A bitset whose first 64 bits are stored inline. Higher bits spill into a heap
+/// allocated tail that is only created once such a bit is set. The tail is a boxed
+/// slice rather than a Vec to keep cells, which embed one of these, small.
+#[derive(Debug, Clone, Default)]
+pub struct SmallBitSet {
+  inline: u64,
+  spill: Option<Box<[u64]>>,
+}
+
+impl SmallBitSet {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn insert(&mut self, bit: usize) {
+    match bit / 64 {
+      0 => self.inline |= 1 << bit,
+      word => {
+        let spill = self.spill.get_or_insert_with(Default::default);
+        if spill.len() < word {
+          let mut grown = vec![0; word];
+          grown[..spill.len()].copy_from_slice(spill);
+          *spill = grown.into_boxed_slice();
+        }
+        spill[word - 1] |= 1 << (bit % 64);
+      }
+    }
+  }
+
+  pub fn remove(&mut self, bit: usize) {
+    match bit / 64 {
+      0 => self.inline &= !(1 << bit),
+      word => {
+        if let Some(spilled) = self.spill_words_mut().get_mut(word - 1) {
+          *spilled &= !(1 << (bit % 64));
+        }
+      }
+    }
+  }
+
+  pub fn contains(&self, bit: usize) -> bool {
+    match bit / 64 {
+      0 => self.inline & (1 << bit) != 0,
+      word => self
+        .spill_words()
+        .get(word - 1)
+        .is_some_and(|spilled| spilled & (1 << (bit % 64)) != 0),
+    }
+  }
+
+  /// Unsets all bits. The spill allocation is kept for reuse.
+  pub fn clear(&mut self) {
+    self.inline = 0;
+    self
+      .spill_words_mut()
+      .iter_mut()
+      .for_each(|spilled| *spilled = 0);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.inline == 0 && self.spill_words().iter().all(|spilled| *spilled == 0)
+  }
+
+  /// The lowest set bit
+  pub fn first(&self) -> Option<usize> {
+    if self.inline != 0 {
+      return Some(self.inline.trailing_zeros() as usize);
+    }
+    self
+      .spill_words()
+      .iter()
+      .position(|spilled| *spilled != 0)
+      .map(|word| (word + 1) * 64 + self.spill_words()[word].trailing_zeros() as usize)
+  }
+
+  /// The inline bits 0..64
+  pub fn low_bits(&self) -> u64 {
+    self.inline
+  }
+
+  fn spill_words(&self) -> &[u64] {
+    self.spill.as_deref().unwrap_or_default()
+  }
+
+  fn spill_words_mut(&mut self) -> &mut [u64] {
+    self.spill.as_deref_mut().unwrap_or_default()
+  }
+
+  // spill words up to the last non-zero one, so equal sets compare and hash equal
+  // regardless of how much they have spilled
+  fn significant_spill(&self) -> &[u64] {
+    let spill = self.spill_words();
+    let len = spill
+      .iter()
+      .rposition(|spilled| *spilled != 0)
+      .map_or(0, |ix| ix + 1);
+    &spill[..len]
+  }
+}
+
+impl PartialEq for SmallBitSet {
+  fn eq(&self, other: &Self) -> bool {
+    self.inline == other.inline && self.significant_spill() == other.significant_spill()
+  }
+}
+
+impl Eq for SmallBitSet {}
+
+impl Hash for SmallBitSet {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.inline.hash(state);
+    self.significant_spill().hash(state);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bits_beyond_64_spill() {
+    let mut bits = SmallBitSet::new();
+    bits.insert(3);
+    bits.insert(100);
+    assert!(bits.contains(3));
+    assert!(bits.contains(100));
+    assert!(!bits.contains(99));
+    assert!(!bits.contains(1000));
+    assert_eq!(bits.low_bits(), 1 << 3);
+
+    bits.remove(100);
+    assert!(!bits.contains(100));
+    let mut inline_only = SmallBitSet::new();
+    inline_only.insert(3);
+    assert_eq!(bits, inline_only);
+
+    bits.clear();
+    assert!(bits.is_empty());
+  }
+}
@@ -1,12 +1,26 @@
 use std::cell::RefCell;
-use std::hash::Hash;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 use bitflags::bitflags;
+use petgraph::algo::{astar, is_isomorphic_matching};
+use petgraph::graph::EdgeIndex;
 use petgraph::graph::Graph;
 use petgraph::graph::NodeIndex;
+use petgraph::visit::{Dfs, EdgeFiltered, EdgeRef};
+use petgraph::Direction;
 
+use crate::behavior::*;
+use crate::bitset::SmallBitSet;
+use crate::data::{Payload, Value};
+use crate::error::BurstError;
 use crate::instance::Instance;
+use crate::ops::{ArithmeticMode, Operation};
+use crate::rng::SplitMix64;
 
 // TODO: may be time to use differing structures for components and instances
 // since components are more about design-time considerations and instances runtime
@@ -19,9 +33,15 @@ bitflags! {
   }
 }
 
+/// Application specific state that embedders attach to every cell, see `CellNode::user`.
+/// Implemented for every type with the required bounds, `()` when there is none.
+pub trait CellValue: Clone + Debug + Default + PartialEq + 'static {}
+
+impl<V: Clone + Debug + Default + PartialEq + 'static> CellValue for V {}
+
 #[derive(Debug, Clone)]
-pub enum Node {
-  Cell(CellNode),
+pub enum Node<V = ()> {
+  Cell(CellNode<V>),
   ConnectorIn(ConnectorInNode),
   ConnectorOut(ConnectorOutNode),
   Component(InstanceRefNode),
@@ -32,6 +52,8 @@ pub struct InstanceRefNode {
   pub node_name: String,
   pub component_name: Rc<str>,
   pub instance_ix: Option<NodeIndex>,
+  pub pin: Option<InstancePin>,
+  pub max_recursion_depth: Option<usize>,
 }
 
 impl InstanceRefNode {
@@ -40,8 +62,37 @@ impl InstanceRefNode {
       node_name,
       component_name,
       instance_ix: None,
+      pin: None,
+      max_recursion_depth: None,
     }
   }
+
+  /// Gives the referenced instance a stable id that survives reaping. Ids must be unique
+  /// across the orchestrator, so only pin refs of components that are instantiated once.
+  pub fn pinned(mut self, instance_id: Rc<str>, restore_state: bool) -> Self {
+    self.pin = Some(InstancePin {
+      instance_id,
+      restore_state,
+    });
+    self
+  }
+
+  /// Caps how many instances of the referenced component may be nested along one instance
+  /// ancestry path, counting the instance about to be created. Meant for components that
+  /// instantiate themselves.
+  pub fn with_max_recursion_depth(mut self, depth: usize) -> Self {
+    self.max_recursion_depth = Some(depth);
+    self
+  }
+}
+
+/// Identity of a pinned instance. When a pinned instance is reaped and signaled again it is
+/// recreated under the same id. With `restore_state` the reaped instance is parked in the
+/// instance pool and resumed as is, otherwise it starts fresh.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InstancePin {
+  pub instance_id: Rc<str>,
+  pub restore_state: bool,
 }
 
 impl Eq for InstanceRefNode {}
@@ -59,25 +110,30 @@ impl Hash for InstanceRefNode {
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct InstanceComponentIx {
   pub instance_ix: NodeIndex,
   pub component_ix: NodeIndex,
 }
 
 #[derive(Debug)]
-pub struct InstanceGraphNode {
+pub struct InstanceGraphNode<V = ()> {
   pub component_name: String,
-  pub instance: Option<Rc<RefCell<Instance>>>,
+  pub instance: Option<Rc<RefCell<Instance<V>>>>,
+  // copied from the InstanceRefNode this node was created for
+  pub pin: Option<InstancePin>,
+  pub max_recursion_depth: Option<usize>,
+  // the instance whose wiring created this node, None for roots
+  pub parent: Option<NodeIndex>,
 }
 
-impl Hash for InstanceGraphNode {
+impl<V> Hash for InstanceGraphNode<V> {
   fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
     self.component_name.hash(state);
   }
 }
 
-impl PartialEq for InstanceGraphNode {
+impl<V> PartialEq for InstanceGraphNode<V> {
   fn eq(&self, other: &Self) -> bool {
     self.component_name == other.component_name
   }
@@ -87,6 +143,28 @@ impl PartialEq for InstanceGraphNode {
 pub struct ConnectorInNode {
   pub node_name: String,
   pub flags: CellFlags,
+  // named sub-signals (e.g. `con.start` in the DSL) and the signal bits they map to
+  pub fields: BTreeMap<String, u8>,
+  // fields the connector was signaled with this cycle, empty when signaled as a whole
+  pub field_signals: SmallBitSet,
+  // deliver at most one signal per cycle from other instances, see `coalescing`
+  pub coalesce: bool,
+  // data of the last signal that carried any
+  pub payload: Option<Payload>,
+  pub trigger_mode: TriggerMode,
+}
+
+/// How long a ConnectorIn keeps firing once signaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TriggerMode {
+  /// Fires once per signal
+  #[default]
+  Edge,
+  /// Stays asserted and fires every step until released with
+  /// `Orchestrator::release_connector_in`. Signals while asserted are absorbed. Its
+  /// instance never goes idle meanwhile, so `Orchestrator::run` doesn't return while the
+  /// connector is asserted; drive such runs with `tick` or `run_yielding`.
+  Level,
 }
 
 impl ConnectorInNode {
@@ -94,36 +172,174 @@ impl ConnectorInNode {
     ConnectorInNode {
       node_name,
       flags: CellFlags::empty(),
+      fields: BTreeMap::new(),
+      field_signals: SmallBitSet::new(),
+      coalesce: false,
+      payload: None,
+      trigger_mode: TriggerMode::Edge,
     }
   }
+
+  /// Makes the connector level-triggered, see `TriggerMode::Level`
+  pub fn level_triggered(mut self) -> Self {
+    self.trigger_mode = TriggerMode::Level;
+    self
+  }
+
+  /// Makes the connector deliver repeated signals from other instances within one cycle
+  /// only once. Without it every signal is delivered, so e.g. accumulators count repeats.
+  pub fn coalescing(mut self) -> Self {
+    self.coalesce = true;
+    self
+  }
+
+  /// Names the signal bit `signal_bit` of this connector's outgoing edges
+  pub fn with_field(mut self, name: &str, signal_bit: u8) -> Self {
+    self.fields.insert(name.to_string(), signal_bit);
+    self
+  }
 }
 
 #[derive(Debug, Clone)]
 pub struct ConnectorOutNode {
+  pub node_name: String,
   pub to_instance_connector: Option<InstanceComponentIx>,
+  // accepts subscribers, see `broadcasting`
+  pub broadcast: bool,
+  // ConnectorIns of other instances that receive every signal of a broadcast connector
+  pub subscribers: Vec<InstanceComponentIx>,
+  // data sent along with every signal, see `with_payload`
+  pub payload: Option<Payload>,
+  pub fan_out: FanOut,
+  // running weights of the round-robin, one per target, see `FanOut::Distribute`
+  pub distribution: Vec<i64>,
+  // value of the cell that signaled the connector last, see `Orchestrator::read_output`
+  pub output: Option<Value>,
+}
+
+/// How a ConnectorOut hands its signals to its wired target and subscribers
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum FanOut {
+  /// Every target receives every signal
+  #[default]
+  Broadcast,
+  /// Every signal goes to exactly one target, picked by smooth weighted round-robin.
+  /// Targets are the wired one followed by the subscribers in subscription order, the
+  /// n-th target has weight `weights[n]` (1 when missing). Ties go to the earlier target,
+  /// so equal weights alternate in target order. The running weights restart whenever the
+  /// number of targets changes.
+  Distribute { weights: Vec<u32> },
 }
 
 impl ConnectorOutNode {
-  pub fn new() -> ConnectorOutNode {
+  pub fn new(node_name: String) -> ConnectorOutNode {
     ConnectorOutNode {
+      node_name,
       to_instance_connector: None,
+      broadcast: false,
+      subscribers: Vec::new(),
+      payload: None,
+      fan_out: FanOut::Broadcast,
+      distribution: Vec::new(),
+      output: None,
     }
   }
+
+  /// Lets instances subscribe to the connector at runtime (see `Orchestrator::subscribe`).
+  /// Each signal is delivered to every subscriber in addition to the wired target.
+  pub fn broadcasting(mut self) -> Self {
+    self.broadcast = true;
+    self
+  }
+
+  /// Like `broadcasting`, but hands each signal to a single target, see
+  /// `FanOut::Distribute`
+  pub fn distributing(mut self, weights: Vec<u32>) -> Self {
+    self.broadcast = true;
+    self.fan_out = FanOut::Distribute { weights };
+    self
+  }
+
+  /// Picks the target of the next signal of a distributing connector and advances the
+  /// round-robin. Returns None for broadcasting connectors and when no target has weight.
+  pub fn distribute_next(&mut self) -> Option<InstanceComponentIx> {
+    let weights = match &self.fan_out {
+      FanOut::Distribute { weights } => weights,
+      FanOut::Broadcast => return None,
+    };
+    let targets: Vec<_> = self
+      .to_instance_connector
+      .iter()
+      .chain(self.subscribers.iter())
+      .copied()
+      .collect();
+    let weight = |n: usize| i64::from(weights.get(n).copied().unwrap_or(1));
+    let total: i64 = (0..targets.len()).map(weight).sum();
+    if total == 0 {
+      return None;
+    }
+    if self.distribution.len() != targets.len() {
+      self.distribution = vec![0; targets.len()];
+    }
+    for (n, current) in self.distribution.iter_mut().enumerate() {
+      *current += weight(n);
+    }
+    let picked = (0..targets.len()).fold(0, |best, n| {
+      if self.distribution[n] > self.distribution[best] {
+        n
+      } else {
+        best
+      }
+    });
+    self.distribution[picked] -= total;
+    Some(targets[picked])
+  }
+
+  /// Sends `payload` with every signal. Buffers are shared, not copied, between all
+  /// instances of the component and the ConnectorIns they signal.
+  pub fn with_payload(mut self, payload: Payload) -> Self {
+    self.payload = Some(payload);
+    self
+  }
 }
 
-#[derive(Debug, Clone, PartialEq, Hash)]
-pub struct CellNode {
+/// Describes one connector of a component. ConnectorIn nodes are `Incoming`,
+/// ConnectorOut nodes `Outgoing`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectorInfo {
+  pub name: String,
+  pub direction: Direction,
+  pub index: NodeIndex,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellNode<V = ()> {
   pub cell_type: CellType,
   pub flags: CellFlags,
-  pub signals: u32,
+  pub signals: SmallBitSet,
+  pub last_fired_cycle: Option<usize>,
+  // set by routers when they fire, cleared once their signals are propagated
+  pub selected_output: Option<u8>,
+  // running sum of an accumulator's incoming signal weights
+  pub accumulated: i8,
+  // data the cell holds, e.g. a value derived by a `Transform`
+  pub value: Value,
+  // embedder state, carried along but never touched by the engine
+  pub user: V,
 }
 
+// constructors make cells without embedder state, `with_user` attaches it
 impl CellNode {
   fn new(tp: CellType) -> Self {
     Self {
       cell_type: tp,
       flags: CellFlags::empty(),
-      signals: 0,
+      signals: SmallBitSet::new(),
+      last_fired_cycle: None,
+      selected_output: None,
+      accumulated: 0,
+      value: Value::default(),
+      user: (),
     }
   }
 
@@ -135,43 +351,240 @@ impl CellNode {
     Self::new(CellType::OneShot)
   }
 
+  pub fn sensor() -> Self {
+    Self::new(CellType::Sensor)
+  }
+
+  pub fn router() -> Self {
+    Self::new(CellType::Router)
+  }
+
+  /// A one shot that refuses to fire again until `cooldown` instance cycles have passed
+  pub fn one_shot_rearm(cooldown: u32) -> Self {
+    Self::new(CellType::OneShotRearm { cooldown })
+  }
+
+  /// Sums the weights of incoming signals and fires once the sum reaches `threshold`
+  pub fn accumulator(threshold: i8, overflow: OverflowPolicy) -> Self {
+    Self::new(CellType::Accumulator {
+      threshold,
+      overflow,
+    })
+  }
+
+  /// Fires once signals on bits 0..`inputs` arrived, keeping them across cycles
+  pub fn latch(inputs: u8) -> Self {
+    Self::new(CellType::Latch { inputs })
+  }
+
+  /// Fires with the given probability each time it is processed, drawing from the
+  /// instance's RNG
+  pub fn random_gate(probability: f32) -> Self {
+    Self::new(CellType::RandomGate { probability })
+  }
+
+  /// Computes `op` over two cells' values into a third one's, see `CellType::Compute`.
+  /// Fails with `BurstError::ComputeIndexOutOfRange` if a cell's index doesn't fit into a
+  /// u16.
+  pub fn compute(
+    op: Operation,
+    operands: [NodeIndex; 2],
+    out: NodeIndex,
+  ) -> Result<Self, BurstError> {
+    let compact = |node: NodeIndex| {
+      u16::try_from(node.index()).map_err(|_| BurstError::ComputeIndexOutOfRange { node })
+    };
+    Ok(Self::new(CellType::Compute {
+      op,
+      operands: [compact(operands[0])?, compact(operands[1])?],
+      out: compact(out)?,
+    }))
+  }
+}
+
+impl<V: CellValue> CellNode<V> {
+  /// Attaches embedder state to the cell, e.g. a label
+  pub fn with_user<W: CellValue>(self, user: W) -> CellNode<W> {
+    CellNode {
+      cell_type: self.cell_type,
+      flags: self.flags,
+      signals: self.signals,
+      last_fired_cycle: self.last_fired_cycle,
+      selected_output: self.selected_output,
+      accumulated: self.accumulated,
+      value: self.value,
+      user,
+    }
+  }
+
   pub fn get_type(&self) -> CellType {
     self.cell_type
   }
 
   pub fn set_signal(&mut self, signal_bit: u8) {
-    self.signals |= 1 << signal_bit;
+    self.signals.insert(signal_bit as usize);
   }
 
   pub fn clear_signal(&mut self, signal_bit: u8) {
-    self.signals &= !(1 << signal_bit);
+    self.signals.remove(signal_bit as usize);
   }
 
   pub fn get_signal(&self, signal_bit: u8) -> bool {
-    self.signals & (1 << signal_bit) != 0
+    self.signals.contains(signal_bit as usize)
   }
 
-  pub fn get_signals(&self) -> u32 {
-    self.signals
+  pub fn get_signals(&self) -> &SmallBitSet {
+    &self.signals
   }
 
   pub fn clear_signals(&mut self) {
-    self.signals = 0;
+    self.signals.clear();
+  }
+
+  /// Adds `weight` to the sum of an accumulator cell, other cells ignore it. Returns false
+  /// if the sum overflowed under `OverflowPolicy::Error`, leaving the sum unchanged.
+  pub fn accumulate(&mut self, weight: i8) -> bool {
+    match self.cell_type {
+      CellType::Accumulator { overflow, .. } => match self.accumulated.checked_add(weight) {
+        Some(accumulated) => {
+          self.accumulated = accumulated;
+          true
+        }
+        None => match overflow {
+          OverflowPolicy::Saturate => {
+            self.accumulated = self.accumulated.saturating_add(weight);
+            true
+          }
+          OverflowPolicy::Error => false,
+        },
+      },
+      _ => true,
+    }
+  }
+
+  /// Runs the behavior of this cell's type
+  pub fn process(&mut self, instance_cycle: usize, rng: &mut SplitMix64) {
+    let cell_type = self.cell_type;
+    let mut ctx = CellContext {
+      flags: &mut self.flags,
+      signals: &self.signals,
+      last_fired_cycle: &mut self.last_fired_cycle,
+      selected_output: &mut self.selected_output,
+      accumulated: &mut self.accumulated,
+      rng,
+      instance_cycle,
+    };
+    cell_type.with_behavior(|behavior| behavior.on_process(&mut ctx));
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CellType {
   Relay,
   OneShot,
+  OneShotRearm {
+    // u32 rather than usize keeps CellType, and thereby CellNode, small
+    cooldown: u32,
+  },
+  Sensor,
+  Router,
+  Accumulator {
+    threshold: i8,
+    overflow: OverflowPolicy,
+  },
+  RandomGate {
+    probability: f32,
+  },
+  Latch {
+    inputs: u8,
+  },
+  /// Fires like a relay and runs `op` on the values of the `operands` cells, writing the
+  /// result into the value of the `out` cell. Operands are read when the compute cell is
+  /// processed, so they are up to date if whatever writes them also signals the compute
+  /// cell, which then runs the cycle after. A failing op is reported as a warning and
+  /// the cell doesn't fire.
+  Compute {
+    op: Operation,
+    // node indices, u16 rather than NodeIndex keeps CellType within 8 bytes
+    operands: [u16; 2],
+    out: u16,
+  },
 }
 
-#[derive(Debug, Clone)]
+/// What an accumulator does when adding a weight overflows its sum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+  /// Clamp the sum to the accumulator's range
+  Saturate,
+  /// Drop the weight and report `BurstError::AccumulatorOverflow` as a warning
+  Error,
+}
+
+impl CellType {
+  /// Calls `f` with the behavior implementing this cell type
+  pub fn with_behavior<R>(self, f: impl FnOnce(&mut dyn CellBehavior) -> R) -> R {
+    match self {
+      CellType::Relay => f(&mut Relay),
+      CellType::OneShot => f(&mut OneShot),
+      CellType::OneShotRearm { cooldown } => f(&mut OneShotRearm {
+        cooldown: cooldown as usize,
+      }),
+      CellType::Sensor => f(&mut Sensor),
+      CellType::Router => f(&mut Router),
+      CellType::Accumulator { threshold, .. } => f(&mut Accumulator { threshold }),
+      CellType::RandomGate { probability } => f(&mut RandomGate { probability }),
+      CellType::Latch { inputs } => f(&mut Latch { inputs }),
+      // the op itself needs the other cells, so the instance runs it once the cell fired
+      CellType::Compute { .. } => f(&mut Relay),
+    }
+  }
+
+  pub fn signal_reset(self) -> SignalReset {
+    self.with_behavior(|behavior| behavior.signal_reset())
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Signal {
   pub signal_bit: u8,
+  // added to the sum of an accumulator receiving the signal
+  pub weight: i8,
 }
 
-#[derive(Debug, Clone)]
+/// Stages the target (typically a sensor) when the source cell fires, see
+/// `stage_associated_nodes`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Association {
+  pub transform: Option<Transform>,
+}
+
+/// Derives the value of an associated cell from the value of the cell it senses. `op` is
+/// applied to a copy of the source's value as operand0 and `operand` as operand1. The
+/// result, operand0 for the `Assign` forms and operand2 otherwise, becomes the target's
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+  pub op: Operation,
+  pub operand: Value,
+}
+
+impl Transform {
+  pub fn new(op: Operation, operand: Value) -> Self {
+    Transform { op, operand }
+  }
+
+  pub fn apply(&self, source: Value, mode: ArithmeticMode) -> Result<Value, BurstError> {
+    let mut value = source;
+    let mut operand = self.operand;
+    let mut output = Value::default();
+    self
+      .op
+      .do_op_in(mode, &mut value, &mut operand, Some(&mut output))?;
+    Ok(if self.op.has_output() { output } else { value })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Connection {
   pub instance_connector_name: Rc<str>,
 }
@@ -184,45 +597,653 @@ impl Connection {
   }
 }
 
-#[derive(Debug, Clone)]
+/// Lets a sensor cell observe a cell of a child instance. The edge runs from a source cell
+/// to the sensor; `instance_ref` is the InstanceRefNode of the child and `cell` the observed
+/// cell within the child's component.
+///
+/// Observation is read-only and resolved at the end of the cycle in which the source fired:
+/// if the observed cell is FIRED at that point, `signal_bit` is set on the sensor and the
+/// sensor is staged for the next cycle. Observing an uninstantiated child does nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observe {
+  pub instance_ref: NodeIndex,
+  pub cell: NodeIndex,
+  pub signal_bit: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Edge {
   Signal(Signal),
-  Association,
+  Association(Association),
   Connection(Connection),
+  Observe(Observe),
 }
 
 impl Edge {
   pub fn new_signal(signal_bit: u8) -> Self {
-    Self::Signal(Signal { signal_bit })
+    Self::Signal(Signal {
+      signal_bit,
+      weight: 1,
+    })
+  }
+
+  pub fn new_weighted_signal(signal_bit: u8, weight: i8) -> Self {
+    Self::Signal(Signal { signal_bit, weight })
   }
 
   pub fn new_association() -> Self {
-    Self::Association
+    Self::Association(Association::default())
+  }
+
+  /// An association that also writes `transform`'s result into the target's value
+  pub fn new_transform(transform: Transform) -> Self {
+    Self::Association(Association {
+      transform: Some(transform),
+    })
+  }
+
+  pub fn new_observe(instance_ref: NodeIndex, cell: NodeIndex, signal_bit: u8) -> Self {
+    Self::Observe(Observe {
+      instance_ref,
+      cell,
+      signal_bit,
+    })
+  }
+}
+
+pub type ComponentGraph<V = ()> = Graph<Node<V>, Edge>;
+
+fn structurally_eq_nodes<V>(a: &Node<V>, b: &Node<V>) -> bool {
+  match (a, b) {
+    (Node::Cell(a), Node::Cell(b)) => a.cell_type == b.cell_type,
+    (Node::ConnectorIn(a), Node::ConnectorIn(b)) => {
+      a.node_name == b.node_name
+        && a.fields == b.fields
+        && a.coalesce == b.coalesce
+        && a.trigger_mode == b.trigger_mode
+    }
+    (Node::ConnectorOut(a), Node::ConnectorOut(b)) => {
+      a.node_name == b.node_name && a.broadcast == b.broadcast && a.fan_out == b.fan_out
+    }
+    (Node::Component(a), Node::Component(b)) => {
+      a.node_name == b.node_name && a.component_name == b.component_name
+    }
+    _ => false,
+  }
+}
+
+fn structurally_eq_edges(a: &Edge, b: &Edge) -> bool {
+  match (a, b) {
+    // `instance_ref` is an index into the graph being matched, so it is not compared
+    (Edge::Observe(a), Edge::Observe(b)) => a.cell == b.cell && a.signal_bit == b.signal_bit,
+    _ => a == b,
   }
 }
 
-pub type ComponentGraph = Graph<Node, Edge>;
+fn hash_of(value: &impl Hash) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  value.hash(&mut hasher);
+  hasher.finish()
+}
+
+/// Hashes what `structurally_eq_nodes` compares. The cells a compute cell refers to are
+/// left to `Component::content_hash`, which knows their labels rather than their indices.
+fn structural_node_hash<V>(node: &Node<V>) -> u64 {
+  match node {
+    Node::Cell(CellNode {
+      cell_type: CellType::Compute { op, .. },
+      ..
+    }) => hash_of(&(4, *op as u32)),
+    // CellType holds floats, its Debug output is what is stable about it
+    Node::Cell(cell) => hash_of(&(0, format!("{:?}", cell.cell_type))),
+    Node::ConnectorIn(connector) => hash_of(&(
+      1,
+      &connector.node_name,
+      &connector.fields,
+      connector.coalesce,
+      connector.trigger_mode,
+    )),
+    Node::ConnectorOut(connector) => hash_of(&(
+      2,
+      &connector.node_name,
+      connector.broadcast,
+      &connector.fan_out,
+    )),
+    Node::Component(instance_ref) => {
+      hash_of(&(3, &instance_ref.node_name, &instance_ref.component_name))
+    }
+  }
+}
+
+/// Hashes what `structurally_eq_edges` compares, leaving out node indices
+fn structural_edge_hash(edge: &Edge) -> u64 {
+  match edge {
+    Edge::Observe(observe) => hash_of(&("Observe", observe.signal_bit)),
+    _ => hash_of(&format!("{:?}", edge)),
+  }
+}
 
 #[derive(Debug, Clone)]
-pub struct Component {
+pub struct Component<V = ()> {
   pub name: Rc<str>,
-  pub graph: ComponentGraph,
+  pub graph: ComponentGraph<V>,
+  // cells staged whenever a new instance is created
+  pub init_cells: Vec<NodeIndex>,
+  // stage associated (sensor) cells ahead of signaled ones, see `set_sense_before_update`
+  pub sense_before_update: bool,
+  // names of the interfaces this component declares, see `Interface::check`
+  pub interfaces: Vec<Rc<str>>,
+  // named constants, e.g. the DSL's `const threshold: usize = 2;`
+  pub constants: HashMap<String, Value>,
+  // whether the graph contains InstanceRefNodes, recomputed when an orchestrator
+  // registers the component
+  pub has_instance_refs: bool,
+  // ConnectorIns by name, resolved when an orchestrator registers the component so
+  // wiring child instances doesn't scan the graph
+  pub connector_in_indices: Rc<HashMap<String, NodeIndex>>,
+  // signal bit of edges added with `add_signal`
+  pub default_signal_bit: u8,
+  // one-hot sets of cells, see `add_exclusive_group`
+  pub exclusive_groups: Vec<Vec<NodeIndex>>,
   // cell_info_map: HashMap<String, CellInfo>,
 }
 
 impl Component {
   pub fn new(name: &str) -> Self {
+    Self::named(name)
+  }
+}
+
+impl<V: CellValue> Component<V> {
+  /// Like `Component::new`, for components whose cells carry a `V`
+  pub fn named(name: &str) -> Self {
     Component {
       name: Rc::from(name),
       graph: Graph::new(),
+      init_cells: Vec::new(),
+      sense_before_update: false,
+      interfaces: Vec::new(),
+      constants: HashMap::new(),
+      has_instance_refs: true,
+      connector_in_indices: Rc::default(),
+      default_signal_bit: 0,
+      exclusive_groups: Vec::new(),
       // cell_info_map: HashMap::new(),
     }
   }
+
+  /// By default sensors are staged after the cells signaled by the same node, so they see
+  /// the updated state. With `sense_before_update` they are staged first and see the state
+  /// from before the update.
+  pub fn set_sense_before_update(&mut self, sense_before_update: bool) -> &mut Self {
+    self.sense_before_update = sense_before_update;
+    self
+  }
+
+  /// Sets the signal bit used by `add_signal`. Edges added before keep their bit.
+  pub fn set_default_signal_bit(&mut self, signal_bit: u8) -> &mut Self {
+    self.default_signal_bit = signal_bit;
+    self
+  }
+
+  /// Adds a signal edge carrying the default signal bit, the same as adding
+  /// `Edge::new_signal(default_signal_bit)`
+  pub fn add_signal(&mut self, from: NodeIndex, to: NodeIndex) -> EdgeIndex {
+    self
+      .graph
+      .add_edge(from, to, Edge::new_signal(self.default_signal_bit))
+  }
+
+  /// Makes the cells one-hot: at most one of them fires per step. When several would fire,
+  /// the one listed first wins and the others are suppressed after being processed, so
+  /// state their behavior updated (e.g. a rearm's cooldown) is kept.
+  pub fn add_exclusive_group(&mut self, cells: &[NodeIndex]) -> &mut Self {
+    self.exclusive_groups.push(cells.to_vec());
+    self
+  }
+
+  /// Declares that this component implements the named interface
+  pub fn implements(&mut self, interface_name: &str) -> &mut Self {
+    self.interfaces.push(Rc::from(interface_name));
+    self
+  }
+
+  pub fn set_constant(&mut self, name: &str, value: Value) -> &mut Self {
+    self.constants.insert(name.to_string(), value);
+    self
+  }
+
+  pub fn get_constant(&self, name: &str) -> Option<Value> {
+    self.constants.get(name).copied()
+  }
+
+  /// Adds an accumulator cell whose threshold is the constant `threshold`, read as an i8
+  pub fn add_accumulator(
+    &mut self,
+    threshold: &str,
+    overflow: OverflowPolicy,
+  ) -> Result<NodeIndex, BurstError> {
+    let value = self
+      .get_constant(threshold)
+      .ok_or_else(|| BurstError::ConstantNotFound {
+        component: self.name.clone(),
+        constant: threshold.to_string(),
+      })?;
+    Ok(self.graph.add_node(Node::Cell(
+      CellNode::accumulator(*value.as_i8(), overflow).with_user(V::default()),
+    )))
+  }
+
+  /// Marks a cell to be staged as soon as an instance of this component is created
+  pub fn mark_init_cell(&mut self, cell_ix: NodeIndex) -> &mut Self {
+    if !self.init_cells.contains(&cell_ix) {
+      self.init_cells.push(cell_ix);
+    }
+    self
+  }
+
+  /// Marks a one-shot cell as an init trigger, fired by the first step of every instance.
+  /// Init triggers fire in the order they were added, and ahead of cells signaled through
+  /// connectors during that step.
+  pub fn add_init_trigger(&mut self, cell_ix: NodeIndex) -> Result<&mut Self, BurstError> {
+    match self.graph.node_weight(cell_ix) {
+      Some(Node::Cell(cell)) if cell.cell_type == CellType::OneShot => {
+        Ok(self.mark_init_cell(cell_ix))
+      }
+      _ => Err(BurstError::UnexpectedNodeType {
+        node: cell_ix,
+        expected: "OneShot cell",
+      }),
+    }
+  }
+
+  pub fn cell_count(&self) -> usize {
+    self.count_nodes(|node| matches!(node, Node::Cell(_)))
+  }
+
+  pub fn connector_in_count(&self) -> usize {
+    self.count_nodes(|node| matches!(node, Node::ConnectorIn(_)))
+  }
+
+  pub fn connector_out_count(&self) -> usize {
+    self.count_nodes(|node| matches!(node, Node::ConnectorOut(_)))
+  }
+
+  /// Number of InstanceRefNodes, i.e. child instances each instance of this component has
+  pub fn instance_ref_count(&self) -> usize {
+    self.count_nodes(|node| matches!(node, Node::Component(_)))
+  }
+
+  /// Whether both graphs are the same up to node numbering, e.g. a component built in a
+  /// different insertion order. Nodes match by type, cell type and names, edges by their
+  /// signal bits and transforms. Runtime state like flags and values is ignored.
+  pub fn structurally_eq(&self, other: &Component<V>) -> bool {
+    is_isomorphic_matching(
+      &self.graph,
+      &other.graph,
+      structurally_eq_nodes,
+      structurally_eq_edges,
+    )
+  }
+
+  /// A hash of the structure `structurally_eq` compares plus what else decides how
+  /// instances run: init cells and their order, exclusive groups, constants and
+  /// `sense_before_update`. The name, interfaces and `default_signal_bit` are left out, as
+  /// are caches derived from the graph. Components that only differ in node numbering hash
+  /// the same. Nodes are labeled by their neighborhoods, refined until the labels stop
+  /// telling more nodes apart, so the hash doesn't depend on petgraph's iteration order.
+  /// Hashes are stable across runs of the same build, not across Rust versions.
+  pub fn content_hash(&self) -> u64 {
+    let graph = &self.graph;
+    let distinct = |labels: &[u64]| labels.iter().collect::<HashSet<_>>().len();
+    let mut labels: Vec<u64> = graph
+      .node_indices()
+      .map(|ix| {
+        let init_positions: Vec<_> = self
+          .init_cells
+          .iter()
+          .enumerate()
+          .filter(|(_, cell)| **cell == ix)
+          .map(|(position, _)| position)
+          .collect();
+        hash_of(&(structural_node_hash(&graph[ix]), init_positions))
+      })
+      .collect();
+    let mut classes = distinct(&labels);
+    for _ in 0..graph.node_count() {
+      let refined: Vec<u64> = graph
+        .node_indices()
+        .map(|ix| {
+          let mut neighborhood: Vec<_> = graph
+            .edges_directed(ix, Direction::Outgoing)
+            .map(|edge| {
+              (
+                0,
+                structural_edge_hash(edge.weight()),
+                labels[edge.target().index()],
+              )
+            })
+            .chain(graph.edges_directed(ix, Direction::Incoming).map(|edge| {
+              (
+                1,
+                structural_edge_hash(edge.weight()),
+                labels[edge.source().index()],
+              )
+            }))
+            .collect();
+          neighborhood.sort_unstable();
+          // a compute cell's operands and output are ordered, unlike its edges
+          let computed = match &graph[ix] {
+            Node::Cell(CellNode {
+              cell_type: CellType::Compute { operands, out, .. },
+              ..
+            }) => {
+              let label = |cell: u16| labels.get(cell as usize).copied();
+              Some([label(operands[0]), label(operands[1]), label(*out)])
+            }
+            _ => None,
+          };
+          hash_of(&(labels[ix.index()], neighborhood, computed))
+        })
+        .collect();
+      let refined_classes = distinct(&refined);
+      labels = refined;
+      if refined_classes == classes {
+        break;
+      }
+      classes = refined_classes;
+    }
+    let mut exclusive_groups: Vec<_> = self
+      .exclusive_groups
+      .iter()
+      .map(|group| {
+        let mut members: Vec<_> = group
+          .iter()
+          .map(|cell| labels.get(cell.index()).copied())
+          .collect();
+        members.sort_unstable();
+        members
+      })
+      .collect();
+    exclusive_groups.sort_unstable();
+    let mut constants: Vec<_> = self
+      .constants
+      .iter()
+      .map(|(name, value)| (name, format!("{:?}", value)))
+      .collect();
+    constants.sort_unstable();
+    labels.sort_unstable();
+    hash_of(&(
+      labels,
+      exclusive_groups,
+      constants,
+      self.sense_before_update,
+    ))
+  }
+
+  /// Signal edges as `(edge, source, target, signal)`, in edge index order
+  pub fn signal_edges(&self) -> impl Iterator<Item = (EdgeIndex, NodeIndex, NodeIndex, &Signal)> {
+    self.edges_of(|edge| match edge {
+      Edge::Signal(signal) => Some(signal),
+      _ => None,
+    })
+  }
+
+  /// Association edges as `(edge, source, target, association)`, in edge index order
+  pub fn association_edges(
+    &self,
+  ) -> impl Iterator<Item = (EdgeIndex, NodeIndex, NodeIndex, &Association)> {
+    self.edges_of(|edge| match edge {
+      Edge::Association(association) => Some(association),
+      _ => None,
+    })
+  }
+
+  /// Connection edges as `(edge, source, target, connection)`, in edge index order
+  pub fn connection_edges(
+    &self,
+  ) -> impl Iterator<Item = (EdgeIndex, NodeIndex, NodeIndex, &Connection)> {
+    self.edges_of(|edge| match edge {
+      Edge::Connection(connection) => Some(connection),
+      _ => None,
+    })
+  }
+
+  fn edges_of<'a, T: 'a>(
+    &'a self,
+    variant: fn(&Edge) -> Option<&T>,
+  ) -> impl Iterator<Item = (EdgeIndex, NodeIndex, NodeIndex, &'a T)> {
+    self.graph.edge_references().filter_map(move |edge| {
+      Some((
+        edge.id(),
+        edge.source(),
+        edge.target(),
+        variant(edge.weight())?,
+      ))
+    })
+  }
+
+  fn count_nodes(&self, predicate: impl Fn(&Node<V>) -> bool) -> usize {
+    self
+      .graph
+      .node_weights()
+      .filter(|node| predicate(node))
+      .count()
+  }
+
+  /// Lists every ConnectorIn and ConnectorOut of this component
+  pub fn connectors(&self) -> Vec<ConnectorInfo> {
+    self
+      .graph
+      .node_indices()
+      .filter_map(|ix| match &self.graph[ix] {
+        Node::ConnectorIn(connector_in) => Some(ConnectorInfo {
+          name: connector_in.node_name.clone(),
+          direction: Direction::Incoming,
+          index: ix,
+        }),
+        Node::ConnectorOut(connector_out) => Some(ConnectorInfo {
+          name: connector_out.node_name.clone(),
+          direction: Direction::Outgoing,
+          index: ix,
+        }),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// Reports connector names used by more than one ConnectorIn or ConnectorOut. Lookups by
+  /// name only ever find the first of them.
+  pub fn validate_connector_names(&self) -> Result<(), Vec<String>> {
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    for connector in self.connectors() {
+      if !seen.insert(connector.name.clone()) && !duplicates.contains(&connector.name) {
+        duplicates.push(connector.name);
+      }
+    }
+    if duplicates.is_empty() {
+      Ok(())
+    } else {
+      Err(duplicates)
+    }
+  }
+
+  /// Maps the names of all ConnectorIns to their indices. With duplicate names the first
+  /// connector wins, like with `connector_in_index`.
+  pub fn resolve_connector_ins(&self) -> HashMap<String, NodeIndex> {
+    let mut indices = HashMap::new();
+    for ix in self.graph.node_indices() {
+      if let Node::ConnectorIn(connector_in) = &self.graph[ix] {
+        indices.entry(connector_in.node_name.clone()).or_insert(ix);
+      }
+    }
+    indices
+  }
+
+  /// Finds the ConnectorIn node with the given name
+  pub fn connector_in_index(&self, connector_name: &str) -> Option<NodeIndex> {
+    self.graph.node_indices().find(|ix| match &self.graph[*ix] {
+      Node::ConnectorIn(connector_in) => connector_in.node_name == connector_name,
+      _ => false,
+    })
+  }
+
+  /// Returns the cells that can never be staged when signals enter through
+  /// `entry_connectors` (every ConnectorIn if empty) or init cells
+  pub fn unreachable_nodes(&self, entry_connectors: &[NodeIndex]) -> Vec<NodeIndex> {
+    let propagating = EdgeFiltered::from_fn(&self.graph, |edge| {
+      matches!(
+        edge.weight(),
+        Edge::Signal(_) | Edge::Association(_) | Edge::Observe(_)
+      )
+    });
+
+    let mut roots: Vec<NodeIndex> = if entry_connectors.is_empty() {
+      self
+        .graph
+        .node_indices()
+        .filter(|ix| matches!(self.graph[*ix], Node::ConnectorIn(_)))
+        .collect()
+    } else {
+      entry_connectors.to_vec()
+    };
+    roots.extend_from_slice(&self.init_cells);
+
+    let mut dfs = Dfs::empty(&propagating);
+    for root in roots {
+      dfs.move_to(root);
+      while dfs.next(&propagating).is_some() {}
+    }
+
+    self
+      .graph
+      .node_indices()
+      .filter(|ix| matches!(self.graph[*ix], Node::Cell(_)) && !dfs.discovered.contains(ix.index()))
+      .collect()
+  }
+
+  /// Returns the shortest chain of nodes from `from` to `to`, both included, along Signal
+  /// and Association edges, or None if `to` can't be reached within an instance. Each edge
+  /// along the way roughly takes a cycle.
+  pub fn signal_path(&self, from: NodeIndex, to: NodeIndex) -> Option<Vec<NodeIndex>> {
+    let propagating = EdgeFiltered::from_fn(&self.graph, |edge| {
+      matches!(edge.weight(), Edge::Signal(_) | Edge::Association(_))
+    });
+    astar(&propagating, from, |ix| ix == to, |_| 1, |_| 0).map(|(_, path)| path)
+  }
+
+  /// Adds the cells and connects each one to the next with a signal on `bit`
+  pub fn chain(&mut self, cells: &[CellNode<V>], bit: u8) -> Vec<NodeIndex> {
+    let chain: Vec<_> = cells
+      .iter()
+      .map(|cell| self.graph.add_node(Node::Cell(cell.clone())))
+      .collect();
+    for pair in chain.windows(2) {
+      self.graph.add_edge(pair[0], pair[1], Edge::new_signal(bit));
+    }
+    chain
+  }
+
+  /// Like `chain`, but also signals the first cell from an existing node such as a
+  /// ConnectorIn. The returned indices don't include `start_ix`.
+  pub fn chain_from(
+    &mut self,
+    start_ix: NodeIndex,
+    cells: &[CellNode<V>],
+    bit: u8,
+  ) -> Vec<NodeIndex> {
+    let chain = self.chain(cells, bit);
+    if let Some(first) = chain.first() {
+      self.graph.add_edge(start_ix, *first, Edge::new_signal(bit));
+    }
+    chain
+  }
+
+  /// Signals each of `targets` from `source`, each on its own bit. The counterpart of
+  /// `chain` for one cell fanning out to many.
+  pub fn fan_out(&mut self, source: NodeIndex, targets: &[(NodeIndex, u8)]) -> Vec<EdgeIndex> {
+    targets
+      .iter()
+      .map(|(target, bit)| self.graph.add_edge(source, *target, Edge::new_signal(*bit)))
+      .collect()
+  }
+
+  /// Returns non-sensor cells that fire into nothing, i.e. have no outgoing Signal,
+  /// Association or Observe edge. These are usually wiring mistakes.
+  pub fn dangling_fire_nodes(&self) -> Vec<NodeIndex> {
+    self
+      .graph
+      .node_indices()
+      .filter(|ix| match &self.graph[*ix] {
+        Node::Cell(cell) => {
+          cell.cell_type != CellType::Sensor
+            && !self.graph.edges(*ix).any(|edge| {
+              matches!(
+                edge.weight(),
+                Edge::Signal(_) | Edge::Association(_) | Edge::Observe(_)
+              )
+            })
+        }
+        _ => false,
+      })
+      .collect()
+  }
+
+  /// Connects a ConnectorOut to the named ConnectorIn of a child instance.
+  /// The connector name is checked against `target` here rather than when the child is
+  /// lazily wired in `Orchestrator::get_instance`.
+  pub fn connect_out_to(
+    &mut self,
+    out_ix: NodeIndex,
+    instance_ix: NodeIndex,
+    target: &Component<V>,
+    connector_name: &str,
+  ) -> Result<EdgeIndex, BurstError> {
+    match self.graph.node_weight(out_ix) {
+      Some(Node::ConnectorOut(_)) => {}
+      _ => {
+        return Err(BurstError::UnexpectedNodeType {
+          node: out_ix,
+          expected: "ConnectorOut",
+        })
+      }
+    }
+    match self.graph.node_weight(instance_ix) {
+      Some(Node::Component(instance_ref_node)) => {
+        if instance_ref_node.component_name != target.name {
+          return Err(BurstError::ComponentMismatch {
+            node: instance_ix,
+            expected: instance_ref_node.component_name.clone(),
+            found: target.name.clone(),
+          });
+        }
+      }
+      _ => {
+        return Err(BurstError::UnexpectedNodeType {
+          node: instance_ix,
+          expected: "Component",
+        })
+      }
+    }
+    if target.connector_in_index(connector_name).is_none() {
+      return Err(BurstError::ConnectorNotFound {
+        component: target.name.clone(),
+        connector: connector_name.to_string(),
+      });
+    }
+    Ok(self.graph.add_edge(
+      out_ix,
+      instance_ix,
+      Edge::Connection(Connection::new(connector_name.to_string())),
+    ))
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::testing::*;
 
   #[test]
   fn it_works() {
@@ -239,6 +1260,424 @@ mod tests {
       .add_edge(cell_a, cell_b, Edge::new_signal(0));
   }
 
+  #[test]
+  fn unreachable_nodes_reports_orphaned_cells() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let cell_a = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let sensor = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let orphan = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let orphan_target = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(connector_in, cell_a, Edge::new_signal(0));
+    component
+      .graph
+      .add_edge(cell_a, sensor, Edge::new_association());
+    component
+      .graph
+      .add_edge(orphan, orphan_target, Edge::new_signal(0));
+
+    assert_eq!(
+      component.unreachable_nodes(&[]),
+      vec![orphan, orphan_target]
+    );
+    assert_eq!(
+      component.unreachable_nodes(&[connector_in]),
+      vec![orphan, orphan_target]
+    );
+
+    component.mark_init_cell(orphan);
+    assert!(component.unreachable_nodes(&[]).is_empty());
+  }
+
+  #[test]
+  fn compute_cell_rejects_wide_indices() {
+    let wide = NodeIndex::new(u16::MAX as usize + 1);
+    let narrow = NodeIndex::new(u16::MAX as usize);
+    let op = Operation::AddSelfI32OtherI32OutI32;
+    assert!(CellNode::compute(op, [narrow, narrow], narrow).is_ok());
+    assert_eq!(
+      CellNode::compute(op, [narrow, wide], narrow).err(),
+      Some(BurstError::ComputeIndexOutOfRange { node: wide })
+    );
+    assert_eq!(
+      CellNode::compute(op, [narrow, narrow], wide).err(),
+      Some(BurstError::ComputeIndexOutOfRange { node: wide })
+    );
+  }
+
+  #[test]
+  fn cell_node_stays_compact() {
+    // every instance clones its component's cells, keep them within a cache line
+    assert!(std::mem::size_of::<CellNode>() <= 64);
+  }
+
+  /// Run with `cargo test --release -- --ignored --nocapture clone_10k` to compare layouts
+  #[test]
+  #[ignore]
+  fn bench_clone_10k_cell_component() {
+    let mut component = Component::new("AComponent");
+    component.chain(&vec![CellNode::relay(); 10_000], 0);
+    let start = std::time::Instant::now();
+    for _ in 0..100 {
+      std::hint::black_box(component.clone());
+    }
+    println!("cloning a 10k cell component: {:?}", start.elapsed() / 100);
+  }
+
+  #[test]
+  fn cell_signals_beyond_64_bits() {
+    let mut cell = CellNode::relay();
+    cell.set_signal(100);
+    assert!(cell.get_signal(100));
+    // same bit position within the inline word
+    assert!(!cell.get_signal(36));
+    cell.set_signal(2);
+    cell.clear_signal(100);
+    assert!(!cell.get_signal(100));
+    assert_eq!(cell.get_signals().low_bits(), 1 << 2);
+    cell.clear_signals();
+    assert!(cell.get_signals().is_empty());
+  }
+
+  #[test]
+  fn accumulator_threshold_from_constant() {
+    let mut component = Component::new("AComponent");
+    assert_eq!(
+      component.add_accumulator("threshold", OverflowPolicy::Saturate),
+      Err(BurstError::ConstantNotFound {
+        component: Rc::from("AComponent"),
+        constant: "threshold".to_string(),
+      })
+    );
+
+    component.set_constant("threshold", Value::from_i8(2));
+    assert_eq!(component.get_constant("threshold"), Some(Value::from_i8(2)));
+    let cell = component
+      .add_accumulator("threshold", OverflowPolicy::Saturate)
+      .unwrap();
+    match &component.graph[cell] {
+      Node::Cell(cell) => assert_eq!(
+        cell.cell_type,
+        CellType::Accumulator {
+          threshold: 2,
+          overflow: OverflowPolicy::Saturate,
+        }
+      ),
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn dangling_fire_nodes_skips_sensors() {
+    let mut component = Component::new("AComponent");
+    let cell_a = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let terminal_relay = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let terminal_sensor = component.graph.add_node(Node::Cell(CellNode::sensor()));
+    component
+      .graph
+      .add_edge(cell_a, terminal_relay, Edge::new_signal(0));
+    component
+      .graph
+      .add_edge(cell_a, terminal_sensor, Edge::new_association());
+
+    assert_eq!(component.dangling_fire_nodes(), vec![terminal_relay]);
+  }
+
+  #[test]
+  fn connect_out_to_validates_connector_name() {
+    let mut child = Component::new("Child");
+    child.graph.add_node(Node::ConnectorIn(ConnectorInNode::new(
+      "connector_in".to_string(),
+    )));
+
+    let mut parent = Component::new("Parent");
+    let connector_out = parent
+      .graph
+      .add_node(Node::ConnectorOut(ConnectorOutNode::new(
+        "connector_out".to_string(),
+      )));
+    let child_ref = parent.graph.add_node(Node::Component(InstanceRefNode::new(
+      "child".to_string(),
+      child.name.clone(),
+    )));
+
+    assert_eq!(
+      parent.connect_out_to(connector_out, child_ref, &child, "no_such_connector"),
+      Err(BurstError::ConnectorNotFound {
+        component: child.name.clone(),
+        connector: "no_such_connector".to_string(),
+      })
+    );
+    assert_eq!(
+      parent.connect_out_to(child_ref, child_ref, &child, "connector_in"),
+      Err(BurstError::UnexpectedNodeType {
+        node: child_ref,
+        expected: "ConnectorOut",
+      })
+    );
+    assert_eq!(parent.graph.edge_count(), 0);
+
+    assert!(parent
+      .connect_out_to(connector_out, child_ref, &child, "connector_in")
+      .is_ok());
+    assert_eq!(parent.graph.edge_count(), 1);
+  }
+
+  #[test]
+  fn signal_path_length_matches_cycles() {
+    let mut component = Component::new("Chain");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let chain = component.chain_from(connector_in, &vec![CellNode::relay(); 4], 0);
+    let last = *chain.last().unwrap();
+    let unconnected = component.graph.add_node(Node::Cell(CellNode::relay()));
+
+    let path = component.signal_path(connector_in, last).unwrap();
+    assert_eq!(path.first(), Some(&connector_in));
+    assert_eq!(&path[1..], chain.as_slice());
+    assert_eq!(component.signal_path(connector_in, unconnected), None);
+    assert_eq!(component.signal_path(last, connector_in), None);
+
+    let mut orchestrator = crate::orchestrator::Orchestrator::new();
+    orchestrator
+      .add_root_component(component)
+      .signal_root_instance_connector_in(connector_in)
+      .run();
+    assert_eq!(orchestrator.context().cycle(), path.len());
+  }
+
+  #[test]
+  fn fan_out_signals_every_target() {
+    let mut component = Component::new("FanOut");
+    let source = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+    let targets: Vec<_> = (0..4)
+      .map(|bit| {
+        let target = component
+          .graph
+          .add_node(Node::Cell(CellNode::one_shot_rearm(0)));
+        (target, bit)
+      })
+      .collect();
+    let edges = component.fan_out(source, &targets);
+    assert_eq!(edges.len(), 4);
+    for (edge, (target, bit)) in edges.iter().zip(targets.iter()) {
+      assert_eq!(
+        component.graph.edge_endpoints(*edge),
+        Some((source, *target))
+      );
+      assert_eq!(component.graph[*edge], Edge::new_signal(*bit));
+    }
+
+    let mut instance =
+      crate::instance::Instance::new("fan_out".to_string(), &component, &[source], 0);
+    let mut context = crate::orchestrator::ExecutionContext::new();
+    while instance.step(&mut context) {}
+    for (target, _) in targets {
+      match &instance.nodes[target] {
+        Node::Cell(cell) => assert!(cell.last_fired_cycle.is_some()),
+        _ => unreachable!(),
+      }
+    }
+  }
+
+  #[test]
+  fn structurally_eq_ignores_insertion_order() {
+    let mut forward = Component::new("Chain");
+    let connector_in = forward
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new("in".to_string())));
+    let relay_a = forward.graph.add_node(Node::Cell(CellNode::relay()));
+    let relay_b = forward.graph.add_node(Node::Cell(CellNode::relay()));
+    forward
+      .graph
+      .add_edge(connector_in, relay_a, Edge::new_signal(0));
+    forward
+      .graph
+      .add_edge(relay_a, relay_b, Edge::new_signal(0));
+
+    let mut backward = Component::new("Chain");
+    let relay_b = backward.graph.add_node(Node::Cell(CellNode::relay()));
+    let relay_a = backward.graph.add_node(Node::Cell(CellNode::relay()));
+    let connector_in = backward
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new("in".to_string())));
+    backward
+      .graph
+      .add_edge(relay_a, relay_b, Edge::new_signal(0));
+    backward
+      .graph
+      .add_edge(connector_in, relay_a, Edge::new_signal(0));
+    assert!(forward.structurally_eq(&backward));
+
+    let mut reversed = backward.clone();
+    let edge = reversed.graph.find_edge(relay_a, relay_b).unwrap();
+    reversed.graph.remove_edge(edge);
+    reversed
+      .graph
+      .add_edge(relay_b, relay_a, Edge::new_signal(0));
+    assert!(!forward.structurally_eq(&reversed));
+
+    let mut renamed = backward;
+    renamed.graph[connector_in] = Node::ConnectorIn(ConnectorInNode::new("start".to_string()));
+    assert!(!forward.structurally_eq(&renamed));
+  }
+
+  #[test]
+  fn content_hash_ignores_insertion_order() {
+    let chain = |reversed: bool, bit: u8| {
+      let mut component = Component::new("Chain");
+      let mut nodes = vec![
+        Node::ConnectorIn(ConnectorInNode::new("in".to_string())),
+        Node::Cell(CellNode::relay()),
+        Node::Cell(CellNode::sensor()),
+      ];
+      if reversed {
+        nodes.reverse();
+      }
+      let mut ixs: Vec<_> = nodes
+        .into_iter()
+        .map(|node| component.graph.add_node(node))
+        .collect();
+      if reversed {
+        ixs.reverse();
+      }
+      component
+        .graph
+        .add_edge(ixs[1], ixs[2], Edge::new_association());
+      component
+        .graph
+        .add_edge(ixs[0], ixs[1], Edge::new_signal(bit));
+      component
+    };
+
+    let forward = chain(false, 0);
+    let mut backward = chain(true, 0);
+    assert_eq!(forward.content_hash(), backward.content_hash());
+    // runtime state is not part of the structure
+    if let Node::Cell(cell) = &mut backward.graph[NodeIndex::new(1)] {
+      cell.value = Value::from_i32(7);
+    }
+    assert_eq!(forward.content_hash(), backward.content_hash());
+    assert_ne!(forward.content_hash(), chain(true, 1).content_hash());
+  }
+
+  #[test]
+  fn content_hash_follows_compute_operands_and_component_settings() {
+    // trigger -> compute, which subtracts `b` from `a` into `out`
+    let subtract = |reversed: bool, swapped: bool| {
+      let mut component = Component::new("Subtract");
+      let cells = [CellNode::relay(), CellNode::sensor(), CellNode::relay()];
+      let mut ixs = [NodeIndex::new(0); 3];
+      let order = if reversed { [2, 1, 0] } else { [0, 1, 2] };
+      for position in order {
+        ixs[position] = component
+          .graph
+          .add_node(Node::Cell(cells[position].clone()));
+      }
+      let operands = if swapped {
+        [ixs[1], ixs[0]]
+      } else {
+        [ixs[0], ixs[1]]
+      };
+      let compute = component.graph.add_node(Node::Cell(
+        CellNode::compute(Operation::SubSelfI32OtherI32OutI32, operands, ixs[2]).unwrap(),
+      ));
+      component.add_signal(ixs[2], compute);
+      component.add_signal(compute, ixs[0]);
+      component
+    };
+
+    let forward = subtract(false, false);
+    assert_eq!(forward.content_hash(), subtract(true, false).content_hash());
+    assert_ne!(forward.content_hash(), subtract(false, true).content_hash());
+
+    let changed = |change: fn(&mut Component)| {
+      let mut component = subtract(false, false);
+      change(&mut component);
+      component.content_hash()
+    };
+    assert_ne!(
+      forward.content_hash(),
+      changed(|component| {
+        component.mark_init_cell(NodeIndex::new(2));
+      })
+    );
+    assert_ne!(
+      forward.content_hash(),
+      changed(|component| {
+        component.set_sense_before_update(true);
+      })
+    );
+    assert_ne!(
+      forward.content_hash(),
+      changed(|component| {
+        component
+          .constants
+          .insert("limit".to_string(), Value::from_i32(1));
+      })
+    );
+    assert_ne!(
+      forward.content_hash(),
+      changed(|component| {
+        component.add_exclusive_group(&[NodeIndex::new(0), NodeIndex::new(2)]);
+      })
+    );
+  }
+
+  #[test]
+  fn default_signal_bit_edges_match_explicit_ones() {
+    let chain = |explicit: bool| {
+      let mut component = Component::new("Chain");
+      let connector_in = component
+        .graph
+        .add_node(Node::ConnectorIn(ConnectorInNode::new("in".to_string())));
+      let relay = component.graph.add_node(Node::Cell(CellNode::relay()));
+      let last = component
+        .graph
+        .add_node(Node::Cell(CellNode::one_shot_rearm(0)));
+      for (from, to) in [(connector_in, relay), (relay, last)] {
+        if explicit {
+          component.graph.add_edge(from, to, Edge::new_signal(0));
+        } else {
+          component.add_signal(from, to);
+        }
+      }
+      (component, connector_in, last)
+    };
+
+    let (explicit, _, _) = chain(true);
+    let (defaulted, connector_in, last) = chain(false);
+    assert_eq!(
+      explicit.graph.edge_weights().collect::<Vec<_>>(),
+      defaulted.graph.edge_weights().collect::<Vec<_>>()
+    );
+
+    let mut instance = isolated_instance(&defaulted, &[]);
+    let mut context = isolated_context();
+    instance.signal_connector_in(connector_in).unwrap();
+    run_instance(&mut instance, &mut context, 10);
+    assert!(matches!(
+      &instance.nodes[last],
+      Node::Cell(cell) if cell.last_fired_cycle.is_some()
+    ));
+
+    let mut other_bit = Component::new("Chain");
+    other_bit.set_default_signal_bit(3);
+    let a = other_bit.graph.add_node(Node::Cell(CellNode::relay()));
+    let b = other_bit.graph.add_node(Node::Cell(CellNode::relay()));
+    let edge = other_bit.add_signal(a, b);
+    assert_eq!(other_bit.graph[edge], Edge::new_signal(3));
+  }
+
   #[test]
   fn parallel_quick_sort() {
     let _def = r#"
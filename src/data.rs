@@ -1,5 +1,11 @@
 use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
 use paste::paste;
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::error::BurstError;
 
 macro_rules! val_as {
   ($($type_name:ty)+) => {
@@ -21,7 +27,67 @@ macro_rules! val_as {
   };
 }
 
-#[derive(Debug, Clone, Copy)]
+macro_rules! val_from {
+  ($($type_name:ty)+) => {
+    paste! {
+      $(
+        /// Creates a value holding the given primitive, remaining bytes are zero
+        #[inline(always)]
+        pub fn [<from_ $type_name:snake>](value: $type_name) -> Value {
+          let mut val = Value::default();
+          *val.[<as_ $type_name:snake _mut>]() = value;
+          val
+        }
+      )*
+    }
+  };
+}
+
+macro_rules! val_pair {
+  ($($type_name:ty)+) => {
+    paste! {
+      $(
+        /// The first two elements of the value interpreted as an array of the given type,
+        /// e.g. the (lo, hi) bounds of a clamp
+        #[inline(always)]
+        pub fn [<as_ $type_name:snake _pair>](&self) -> ($type_name, $type_name) {
+          let (_head, body, _tail) = unsafe { self.bytes.align_to::<$type_name>() };
+          (body[0], body[1])
+        }
+
+        /// Creates a value holding two of the given primitive, remaining bytes are zero
+        #[inline(always)]
+        pub fn [<from_ $type_name:snake _pair>](first: $type_name, second: $type_name) -> Value {
+          let mut val = Value::default();
+          let (_head, body, _tail) = unsafe { val.bytes.align_to_mut::<$type_name>() };
+          body[0] = first;
+          body[1] = second;
+          val
+        }
+      )*
+    }
+  };
+}
+
+macro_rules! val_cmp_as {
+  ($($type_name:ty)+) => {
+    paste! {
+      $(
+        /// Compares both values interpreted as the given type. None if either is NaN.
+        #[inline(always)]
+        pub fn [<cmp_as_ $type_name:snake>](&self, other: &Value) -> Option<Ordering> {
+          self.[<as_ $type_name:snake>]().partial_cmp(other.[<as_ $type_name:snake>]())
+        }
+      )*
+    }
+  };
+}
+
+// align(8) keeps the typed views from landing in align_to's unaligned head
+// Value intentionally has no PartialOrd since byte order says nothing about numeric order,
+// use one of the typed cmp_as_* comparisons instead
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(C, align(8))]
 pub struct Value {
   pub bytes: [u8; 8],
 }
@@ -32,10 +98,9 @@ impl PartialEq for Value {
   }
 }
 
-impl PartialOrd for Value {
-  #[inline]
-  fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
-    PartialOrd::partial_cmp(&self.bytes, &other.bytes)
+impl Hash for Value {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.bytes.hash(state);
   }
 }
 
@@ -48,6 +113,168 @@ pub type F32X2 = [f32; 2];
 
 impl Value {
   val_as!(u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 U16X4 U32X2 I16X4 I32X2 F32X2);
+  val_from!(u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 U16X4 U32X2 I16X4 I32X2 F32X2);
+  val_pair!(u8 u16 u32 i8 i16 i32 f32);
+  val_cmp_as!(u8 u16 u32 u64 i8 i16 i32 i64 f32 f64);
+}
+
+/// Data carried by a connector signal. Values up to 8 bytes travel as a `Scalar`, larger
+/// ones like the DSL's `[u32]` arrays as a `Buffer` that every receiver shares.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Payload {
+  Scalar(Value),
+  Buffer(Rc<[u8]>),
+  // mutated in place by its receivers, see `SharedBuffer`
+  Shared(SharedBuffer),
+}
+
+impl Payload {
+  pub fn from_u32_slice(values: &[u32]) -> Self {
+    let bytes: Vec<u8> = values
+      .iter()
+      .flat_map(|value| value.to_ne_bytes())
+      .collect();
+    Payload::Buffer(Rc::from(bytes))
+  }
+
+  /// The payload interpreted as u32s, a scalar holds two of them. None if the buffer is
+  /// not a whole number of aligned u32s.
+  pub fn as_u32_slice(&self) -> Option<&[u32]> {
+    match self {
+      Payload::Scalar(value) => Some(&value.as_u32_x2()[..]),
+      Payload::Buffer(bytes) => match unsafe { bytes.align_to::<u32>() } {
+        ([], body, []) => Some(body),
+        _ => None,
+      },
+      // only readable through a view
+      Payload::Shared(_) => None,
+    }
+  }
+}
+
+/// An array of u32s, the element type of the DSL's arrays, that instances mutate in place
+/// through `BufferView`s. Clones share the array. Equality and hashing go by identity.
+#[derive(Debug, Clone)]
+pub struct SharedBuffer {
+  inner: Rc<SharedBufferInner>,
+}
+
+#[derive(Debug)]
+struct SharedBufferInner {
+  values: Box<[UnsafeCell<u32>]>,
+  // ranges of the live views by view id
+  views: RefCell<Vec<(u64, Range<usize>)>>,
+  next_view_id: Cell<u64>,
+}
+
+impl SharedBuffer {
+  pub fn from_u32_slice(values: &[u32]) -> Self {
+    SharedBuffer {
+      inner: Rc::new(SharedBufferInner {
+        values: values.iter().copied().map(UnsafeCell::new).collect(),
+        views: RefCell::new(Vec::new()),
+        next_view_id: Cell::new(0),
+      }),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.inner.values.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Creates a view of the elements in `range`, e.g. the `values[..pivot]` handed to a
+  /// recursive instance. Panics if the range is out of bounds.
+  pub fn view(&self, range: Range<usize>) -> BufferView {
+    assert!(
+      range.start <= range.end && range.end <= self.len(),
+      "view {:?} out of bounds of buffer of length {}",
+      range,
+      self.len()
+    );
+    let id = self.inner.next_view_id.get();
+    self.inner.next_view_id.set(id + 1);
+    self.inner.views.borrow_mut().push((id, range.clone()));
+    BufferView {
+      buffer: self.clone(),
+      range,
+      id,
+    }
+  }
+}
+
+impl PartialEq for SharedBuffer {
+  fn eq(&self, other: &SharedBuffer) -> bool {
+    Rc::ptr_eq(&self.inner, &other.inner)
+  }
+}
+
+impl Hash for SharedBuffer {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    Rc::as_ptr(&self.inner).hash(state);
+  }
+}
+
+/// A sub-range of a `SharedBuffer`. Its elements can only be accessed while no other live
+/// view of the same buffer overlaps the range, which keeps mutable access exclusive.
+/// Buffer and range are fixed once `SharedBuffer::view` checked them.
+#[derive(Debug)]
+pub struct BufferView {
+  buffer: SharedBuffer,
+  range: Range<usize>,
+  id: u64,
+}
+
+impl BufferView {
+  pub fn buffer(&self) -> &SharedBuffer {
+    &self.buffer
+  }
+
+  pub fn range(&self) -> Range<usize> {
+    self.range.clone()
+  }
+
+  pub fn as_slice(&self) -> Result<&[u32], BurstError> {
+    self.check_exclusive()?;
+    Ok(unsafe { std::slice::from_raw_parts(self.elements(), self.range.len()) })
+  }
+
+  pub fn as_mut_slice(&mut self) -> Result<&mut [u32], BurstError> {
+    self.check_exclusive()?;
+    Ok(unsafe { std::slice::from_raw_parts_mut(self.elements(), self.range.len()) })
+  }
+
+  // UnsafeCell<u32> has the layout of u32
+  fn elements(&self) -> *mut u32 {
+    unsafe { (self.buffer.inner.values.as_ptr() as *mut u32).add(self.range.start) }
+  }
+
+  fn check_exclusive(&self) -> Result<(), BurstError> {
+    let views = self.buffer.inner.views.borrow();
+    match views.iter().find(|(id, range)| {
+      *id != self.id && range.start < self.range.end && self.range.start < range.end
+    }) {
+      Some((_, other)) => Err(BurstError::AliasingViolation {
+        range: self.range.clone(),
+        other: other.clone(),
+      }),
+      None => Ok(()),
+    }
+  }
+}
+
+impl Drop for BufferView {
+  fn drop(&mut self) {
+    self
+      .buffer
+      .inner
+      .views
+      .borrow_mut()
+      .retain(|(id, _)| *id != self.id);
+  }
 }
 
 #[cfg(test)]
@@ -95,4 +322,89 @@ mod tests {
     let b = val.as_f32_x2_mut();
     assert_eq!(b[1], f);
   }
+
+  #[test]
+  fn test_cmp_as_numeric_order() {
+    let minus_one = Value::from_i32(-1);
+    let one = Value::from_i32(1);
+
+    // raw bytes put -1 (0xff...) above 1
+    assert_eq!(minus_one.bytes.cmp(&one.bytes), Ordering::Greater);
+    assert_eq!(minus_one.cmp_as_i32(&one), Some(Ordering::Less));
+    assert_eq!(minus_one.cmp_as_u32(&one), Some(Ordering::Greater));
+
+    let minus_half = Value::from_f64(-0.5);
+    let half = Value::from_f64(0.5);
+    assert_eq!(minus_half.bytes.cmp(&half.bytes), Ordering::Greater);
+    assert_eq!(minus_half.cmp_as_f64(&half), Some(Ordering::Less));
+
+    let nan = Value::from_f64(f64::NAN);
+    assert_eq!(nan.cmp_as_f64(&half), None);
+  }
+
+  #[test]
+  fn test_payload_as_u32_slice() {
+    let values: Vec<u32> = (0..16).collect();
+    let buffer = Payload::from_u32_slice(&values);
+    assert_eq!(buffer.as_u32_slice(), Some(&values[..]));
+    assert_eq!(buffer.clone(), buffer);
+
+    let scalar = Payload::Scalar(Value::from_u32_pair(1, 2));
+    assert_eq!(scalar.as_u32_slice(), Some(&[1, 2][..]));
+    assert_eq!(Payload::Buffer(Rc::from(vec![0u8; 3])).as_u32_slice(), None);
+  }
+
+  #[test]
+  fn disjoint_buffer_views_mutate_in_place() {
+    let buffer = SharedBuffer::from_u32_slice(&[5, 4, 3, 2, 1]);
+    let mut below = buffer.view(0..2);
+    let mut above = buffer.view(3..5);
+    below.as_mut_slice().unwrap().swap(0, 1);
+    above.as_mut_slice().unwrap().swap(0, 1);
+    drop((below, above));
+    assert_eq!(buffer.view(0..5).as_slice().unwrap(), &[4, 5, 3, 1, 2]);
+  }
+
+  #[test]
+  fn overlapping_buffer_views_are_rejected() {
+    let buffer = SharedBuffer::from_u32_slice(&[5, 4, 3, 2, 1]);
+    let mut first = buffer.view(0..3);
+    let second = buffer.view(2..5);
+    assert_eq!(
+      first.as_mut_slice(),
+      Err(BurstError::AliasingViolation {
+        range: 0..3,
+        other: 2..5,
+      })
+    );
+    assert!(second.as_slice().is_err());
+
+    drop(second);
+    assert_eq!(first.as_mut_slice().unwrap(), &[5, 4, 3]);
+    assert_eq!(Payload::Shared(buffer.clone()), Payload::Shared(buffer));
+  }
+
+  #[test]
+  fn buffer_views_keep_their_range() {
+    let buffer = SharedBuffer::from_u32_slice(&[5, 4, 3, 2, 1]);
+    let mut view = buffer.view(1..3);
+    // the range handed out is a copy, widening it leaves the view alone
+    let mut range = view.range();
+    range.end = 1000;
+    assert_eq!(range, 1..1000);
+    assert_eq!(view.range(), 1..3);
+    assert_eq!(view.buffer(), &buffer);
+    assert_eq!(view.as_mut_slice().unwrap(), &[4, 3]);
+  }
+
+  #[test]
+  fn test_val_from_round_trips() {
+    assert_eq!(Value::default().bytes, [0; 8]);
+    assert_eq!(*Value::from_u32(0x04030201).as_u32(), 0x04030201);
+    assert_eq!(*Value::from_f64(1.5).as_f64(), 1.5);
+    assert_eq!(*Value::from_i32_x2([-1, 2]).as_i32_x2(), [-1, 2]);
+
+    let val = Value::from_u16(0xffff);
+    assert_eq!(*val.as_u64(), 0xffff);
+  }
 }
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use petgraph::graph::NodeIndex;
+
+use crate::component::*;
+
+/// Identifies a node independently of its NodeIndex. Connectors and instance refs are
+/// matched by name, cells by their position among the component's cells.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NodeKey {
+  Cell(usize),
+  ConnectorIn(String),
+  ConnectorOut(String),
+  Component(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EdgeKind {
+  Signal,
+  Association,
+  Connection,
+  Observe,
+}
+
+impl EdgeKind {
+  fn of(edge: &Edge) -> Self {
+    match edge {
+      Edge::Signal(_) => EdgeKind::Signal,
+      Edge::Association(_) => EdgeKind::Association,
+      Edge::Connection(_) => EdgeKind::Connection,
+      Edge::Observe(_) => EdgeKind::Observe,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeKey {
+  pub source: NodeKey,
+  pub target: NodeKey,
+  pub kind: EdgeKind,
+}
+
+/// Differences between two versions of a component. Changed nodes and edges exist in
+/// both versions but carry different data (cell type, signal bit, connector name, ...).
+/// Parallel edges share a key, which is then listed once per added, removed or changed
+/// edge.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentDiff {
+  pub added_nodes: Vec<NodeKey>,
+  pub removed_nodes: Vec<NodeKey>,
+  pub changed_nodes: Vec<NodeKey>,
+  pub added_edges: Vec<EdgeKey>,
+  pub removed_edges: Vec<EdgeKey>,
+  pub changed_edges: Vec<EdgeKey>,
+}
+
+impl ComponentDiff {
+  pub fn is_empty(&self) -> bool {
+    self.added_nodes.is_empty()
+      && self.removed_nodes.is_empty()
+      && self.changed_nodes.is_empty()
+      && self.added_edges.is_empty()
+      && self.removed_edges.is_empty()
+      && self.changed_edges.is_empty()
+  }
+}
+
+impl<V: CellValue> Component<V> {
+  /// Assigns every node its structural key, in node index order
+  pub fn node_keys(&self) -> Vec<(NodeIndex, NodeKey)> {
+    let mut cell_position = 0;
+    self
+      .graph
+      .node_indices()
+      .map(|ix| {
+        let key = match &self.graph[ix] {
+          Node::Cell(_) => {
+            cell_position += 1;
+            NodeKey::Cell(cell_position - 1)
+          }
+          Node::ConnectorIn(connector_in) => NodeKey::ConnectorIn(connector_in.node_name.clone()),
+          Node::ConnectorOut(connector_out) => {
+            NodeKey::ConnectorOut(connector_out.node_name.clone())
+          }
+          Node::Component(instance_ref_node) => {
+            NodeKey::Component(instance_ref_node.node_name.clone())
+          }
+        };
+        (ix, key)
+      })
+      .collect()
+  }
+
+  /// Maps each node of this component to the structurally corresponding node of `other`.
+  /// Nodes without a counterpart are left out.
+  pub fn node_correspondence(&self, other: &Component<V>) -> HashMap<NodeIndex, NodeIndex> {
+    let other_nodes: HashMap<_, _> = other
+      .node_keys()
+      .into_iter()
+      .map(|(ix, key)| (key, ix))
+      .collect();
+    self
+      .node_keys()
+      .into_iter()
+      .filter_map(|(ix, key)| other_nodes.get(&key).map(|other_ix| (ix, *other_ix)))
+      .collect()
+  }
+
+  /// Reports how `other` differs from this component
+  pub fn diff(&self, other: &Component<V>) -> ComponentDiff {
+    let mut diff = ComponentDiff::default();
+
+    let keys = self.node_keys();
+    let other_keys = other.node_keys();
+    let key_map: HashMap<_, _> = keys.iter().cloned().collect();
+    let other_key_map: HashMap<_, _> = other_keys.iter().cloned().collect();
+    let nodes: HashMap<_, _> = keys.iter().map(|(ix, key)| (key, *ix)).collect();
+    let other_nodes: HashMap<_, _> = other_keys.iter().map(|(ix, key)| (key, *ix)).collect();
+
+    for (ix, key) in keys.iter() {
+      match other_nodes.get(key) {
+        Some(other_ix) => {
+          if !same_node_data(&self.graph[*ix], &other.graph[*other_ix]) {
+            diff.changed_nodes.push(key.clone());
+          }
+        }
+        None => diff.removed_nodes.push(key.clone()),
+      }
+    }
+    for (_, key) in other_keys.iter() {
+      if !nodes.contains_key(key) {
+        diff.added_nodes.push(key.clone());
+      }
+    }
+
+    let edges = edge_map(self, &key_map);
+    let other_edges = edge_map(other, &other_key_map);
+    for (key, edges) in edges.iter() {
+      // edges equal on both sides cancel out, the rest is paired up as changed
+      let mut unmatched = other_edges.get(key).cloned().unwrap_or_default();
+      let mut removed = 0;
+      for edge in edges {
+        match unmatched.iter().position(|other_edge| other_edge == edge) {
+          Some(position) => {
+            unmatched.swap_remove(position);
+          }
+          None => removed += 1,
+        }
+      }
+      let changed = removed.min(unmatched.len());
+      let report = [
+        (&mut diff.changed_edges, changed),
+        (&mut diff.removed_edges, removed - changed),
+        (&mut diff.added_edges, unmatched.len() - changed),
+      ];
+      for (keys, count) in report {
+        keys.extend(std::iter::repeat_n(key, count).cloned());
+      }
+    }
+    for (key, other_edges) in other_edges.iter() {
+      if !edges.contains_key(key) {
+        let count = other_edges.len();
+        diff
+          .added_edges
+          .extend(std::iter::repeat_n(key, count).cloned());
+      }
+    }
+    // keep the report independent of hash map iteration order
+    diff.removed_edges.sort();
+    diff.added_edges.sort();
+    diff.changed_edges.sort();
+
+    diff
+  }
+}
+
+fn same_node_data<V>(node: &Node<V>, other: &Node<V>) -> bool {
+  match (node, other) {
+    (Node::Cell(cell), Node::Cell(other_cell)) => cell.cell_type == other_cell.cell_type,
+    (Node::Component(instance_ref_node), Node::Component(other_instance_ref_node)) => {
+      instance_ref_node.component_name == other_instance_ref_node.component_name
+    }
+    _ => true,
+  }
+}
+
+/// Groups the edges of `component` by key, parallel edges end up in the same group
+fn edge_map<'a, V>(
+  component: &'a Component<V>,
+  key_map: &HashMap<NodeIndex, NodeKey>,
+) -> HashMap<EdgeKey, Vec<&'a Edge>> {
+  let mut edges: HashMap<EdgeKey, Vec<&Edge>> = HashMap::new();
+  for ix in component.graph.edge_indices() {
+    let (source, target) = component.graph.edge_endpoints(ix).unwrap();
+    let edge = &component.graph[ix];
+    edges
+      .entry(EdgeKey {
+        source: key_map[&source].clone(),
+        target: key_map[&target].clone(),
+        kind: EdgeKind::of(edge),
+      })
+      .or_default()
+      .push(edge);
+  }
+  edges
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn relay_component() -> Component {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let cell_b = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let cell_c = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let cell_d = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(connector_in, cell_b, Edge::new_signal(0));
+    component
+      .graph
+      .add_edge(cell_b, cell_c, Edge::new_association());
+    component
+      .graph
+      .add_edge(cell_b, cell_d, Edge::new_signal(0));
+    component
+  }
+
+  #[test]
+  fn diff_of_identical_components_is_empty() {
+    assert!(relay_component().diff(&relay_component()).is_empty());
+  }
+
+  #[test]
+  fn diff_reports_added_relay() {
+    let component = relay_component();
+    let mut other = relay_component();
+    let cell_d = NodeIndex::new(3);
+    let cell_e = other.graph.add_node(Node::Cell(CellNode::relay()));
+    other.graph.add_edge(cell_d, cell_e, Edge::new_signal(1));
+
+    let diff = component.diff(&other);
+    assert_eq!(diff.added_nodes, vec![NodeKey::Cell(3)]);
+    assert_eq!(
+      diff.added_edges,
+      vec![EdgeKey {
+        source: NodeKey::Cell(2),
+        target: NodeKey::Cell(3),
+        kind: EdgeKind::Signal,
+      }]
+    );
+    assert!(diff.removed_nodes.is_empty());
+    assert!(diff.removed_edges.is_empty());
+    assert!(diff.changed_nodes.is_empty());
+    assert!(diff.changed_edges.is_empty());
+
+    let reverse = other.diff(&component);
+    assert_eq!(reverse.removed_nodes, vec![NodeKey::Cell(3)]);
+    assert_eq!(reverse.removed_edges.len(), 1);
+  }
+
+  #[test]
+  fn diff_reports_changed_signal_bit() {
+    let component = relay_component();
+    let mut other = relay_component();
+    let edge = other
+      .graph
+      .find_edge(NodeIndex::new(1), NodeIndex::new(3))
+      .unwrap();
+    other.graph[edge] = Edge::new_signal(2);
+
+    let diff = component.diff(&other);
+    assert_eq!(diff.changed_edges.len(), 1);
+    assert_eq!(diff.changed_edges[0].target, NodeKey::Cell(2));
+  }
+
+  #[test]
+  fn diff_tells_parallel_edges_apart() {
+    let parallel_signals = |bits: &[u8]| {
+      let mut component = relay_component();
+      for bit in bits {
+        component
+          .graph
+          .add_edge(NodeIndex::new(2), NodeIndex::new(3), Edge::new_signal(*bit));
+      }
+      component
+    };
+    let key = EdgeKey {
+      source: NodeKey::Cell(1),
+      target: NodeKey::Cell(2),
+      kind: EdgeKind::Signal,
+    };
+
+    let component = parallel_signals(&[0, 1]);
+    assert!(component.diff(&parallel_signals(&[1, 0])).is_empty());
+
+    let diff = component.diff(&parallel_signals(&[0, 1, 2]));
+    assert_eq!(diff.added_edges, vec![key.clone()]);
+    assert!(diff.changed_edges.is_empty());
+
+    let diff = component.diff(&parallel_signals(&[0]));
+    assert_eq!(diff.removed_edges, vec![key.clone()]);
+
+    let diff = component.diff(&parallel_signals(&[2, 1]));
+    assert_eq!(diff.changed_edges, vec![key.clone()]);
+    assert!(diff.added_edges.is_empty() && diff.removed_edges.is_empty());
+
+    let diff = parallel_signals(&[]).diff(&component);
+    assert_eq!(diff.added_edges, vec![key.clone(), key]);
+  }
+}
@@ -0,0 +1,90 @@
+use std::ops::Range;
+use std::rc::Rc;
+
+use petgraph::graph::NodeIndex;
+use thiserror::Error;
+
+use crate::ops::Operation;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum BurstError {
+  #[error("connector `{connector}` not found on component `{component}`")]
+  ConnectorNotFound {
+    component: Rc<str>,
+    connector: String,
+  },
+  #[error("node {node:?} is not a {expected}")]
+  UnexpectedNodeType {
+    node: NodeIndex,
+    expected: &'static str,
+  },
+  #[error("instance node {node:?} references component `{found}`, expected `{expected}`")]
+  ComponentMismatch {
+    node: NodeIndex,
+    expected: Rc<str>,
+    found: Rc<str>,
+  },
+  #[error("instance `{instance}` fired unwired ConnectorOut {node:?}")]
+  UnwiredConnectorOut { instance: Rc<str>, node: NodeIndex },
+  #[error("instance budget of {budget} exceeded")]
+  InstanceBudgetExceeded { budget: usize },
+  #[error("division by zero in {op:?}")]
+  DivisionByZero { op: Operation },
+  #[error("component `{component}` is not registered")]
+  ComponentNotFound { component: Rc<str> },
+  #[error("no instance with id `{instance_id}`")]
+  InstanceNotFound { instance_id: String },
+  #[error("connector {connector:?} has no signal field `{field}`")]
+  SignalFieldNotFound { connector: NodeIndex, field: String },
+  #[error("interface `{interface}` is not registered")]
+  InterfaceNotFound { interface: Rc<str> },
+  #[error("component `{component}` does not implement interface `{interface}` (port {port:?})")]
+  InterfaceNotImplemented {
+    component: Rc<str>,
+    interface: Rc<str>,
+    // the offending port, None if the component doesn't declare the interface
+    port: Option<String>,
+  },
+  #[error("accumulator {node:?} of instance `{instance}` overflowed")]
+  AccumulatorOverflow { instance: Rc<str>, node: NodeIndex },
+  #[error("component `{component}` has no constant `{constant}`")]
+  ConstantNotFound {
+    component: Rc<str>,
+    constant: String,
+  },
+  #[error("instance graph node {instance_ix:?} has no live instance")]
+  InstanceNotInstantiated { instance_ix: NodeIndex },
+  #[error("recursion limit of {depth} reached instantiating `{component}`")]
+  RecursionLimit { component: String, depth: usize },
+  #[error("component `{component}` has several connectors named `{connector}`")]
+  ConnectorNameCollision {
+    component: Rc<str>,
+    connector: String,
+  },
+  #[error("instance `{instance}` has more than {limit} signals pending")]
+  SignalQueueOverflow { instance: Rc<str>, limit: usize },
+  #[error("arithmetic overflow in {op:?}")]
+  ArithmeticOverflow { op: Operation },
+  #[error("ConnectorOut {node:?} of instance {instance_ix:?} can't be wired to the same instance")]
+  SelfLoopConnection {
+    instance_ix: NodeIndex,
+    node: NodeIndex,
+  },
+  #[error("buffer view {range:?} overlaps live view {other:?}")]
+  AliasingViolation {
+    range: Range<usize>,
+    other: Range<usize>,
+  },
+  #[error("register {register} of step {step} is out of range")]
+  InvalidRegister { step: usize, register: usize },
+  #[error("step {step} computes {op:?} without an output register")]
+  MissingOutputRegister { step: usize, op: Operation },
+  #[error("shutting down, no instance of `{component}` is created")]
+  ShuttingDown { component: String },
+  #[error("internal panic: {message}")]
+  InternalPanic { message: String },
+  #[error("instance `{instance}` staged {node:?}, which is not a cell")]
+  NonCellStaged { instance: Rc<str>, node: NodeIndex },
+  #[error("compute cell refers to {node:?}, beyond the u16 range of cell indices")]
+  ComputeIndexOutOfRange { node: NodeIndex },
+}
@@ -0,0 +1,87 @@
+//! The arithmetic engine on its own: a sequence of `Operation`s applied to a register file
+//! of `Value`s, without any component graph.
+
+pub use crate::data::Value;
+pub use crate::ops::{ArithmeticMode, Operation};
+
+use crate::error::BurstError;
+
+/// One step of a program: the operation and the registers of its operand0, operand1 and,
+/// for the `Out` forms, operand2
+pub type Instruction = (Operation, usize, usize, Option<usize>);
+
+/// Applies `ops` in order to `regs`. The `Assign` forms write their result to operand0's
+/// register, the `Out` forms to operand2's. Operands may share a register. Stops at the
+/// first failing operation, leaving the registers as the operations before it left them.
+pub fn evaluate(ops: &[Instruction], regs: &mut [Value]) -> Result<(), BurstError> {
+  evaluate_in(ArithmeticMode::default(), ops, regs)
+}
+
+/// Like `evaluate`, with integer Add, Sub and Mul overflowing according to `mode`
+pub fn evaluate_in(
+  mode: ArithmeticMode,
+  ops: &[Instruction],
+  regs: &mut [Value],
+) -> Result<(), BurstError> {
+  for (step, &(op, reg0, reg1, reg2)) in ops.iter().enumerate() {
+    let register = |reg: usize| {
+      regs.get(reg).copied().ok_or(BurstError::InvalidRegister {
+        step,
+        register: reg,
+      })
+    };
+    let mut operand0 = register(reg0)?;
+    let mut operand1 = register(reg1)?;
+    if op.has_output() {
+      let reg2 = reg2.ok_or(BurstError::MissingOutputRegister { step, op })?;
+      let mut operand2 = register(reg2)?;
+      op.do_op_in(mode, &mut operand0, &mut operand1, Some(&mut operand2))?;
+      regs[reg2] = operand2;
+    } else {
+      op.do_op_in(mode, &mut operand0, &mut operand1, None)?;
+      regs[reg0] = operand0;
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn evaluates_over_registers() {
+    // (a + b) * c
+    let mut regs = [
+      Value::from_u32(2),
+      Value::from_u32(3),
+      Value::from_u32(4),
+      Value::default(),
+    ];
+    let program = [
+      (Operation::AddSelfU32OtherU32OutU32, 0, 1, Some(3)),
+      (Operation::MulAssignSelfU32OtherU32, 3, 2, None),
+    ];
+    evaluate(&program, &mut regs).unwrap();
+    assert_eq!(*regs[3].as_u32(), 20);
+    assert_eq!(*regs[0].as_u32(), 2);
+
+    assert_eq!(
+      evaluate(
+        &[(Operation::AddSelfU32OtherU32OutU32, 0, 1, None)],
+        &mut regs
+      ),
+      Err(BurstError::MissingOutputRegister {
+        step: 0,
+        op: Operation::AddSelfU32OtherU32OutU32,
+      })
+    );
+    assert_eq!(
+      evaluate(&program[1..], &mut regs[..3]),
+      Err(BurstError::InvalidRegister {
+        step: 0,
+        register: 3
+      })
+    );
+  }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Cell activity of all live instances after one clock cycle, for rendering a run as
+/// a timeline (see `Orchestrator::record_frames`). Cells are raw `NodeIndex` values of
+/// their instance's component graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frame {
+  pub cycle: usize,
+  pub instances: Vec<InstanceFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceFrame {
+  pub instance_id: String,
+  // cells processed during the cycle
+  pub active: Vec<usize>,
+  // the active cells that fired
+  pub fired: Vec<usize>,
+}
+
+impl Frame {
+  pub fn instance(&self, instance_id: &str) -> Option<&InstanceFrame> {
+    self
+      .instances
+      .iter()
+      .find(|instance| instance.instance_id == instance_id)
+  }
+}
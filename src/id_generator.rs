@@ -0,0 +1,42 @@
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Produces the ids of new instances. Ids must be unique within an orchestrator.
+pub trait IdGenerator: Debug {
+  fn next_id(&mut self) -> Rc<str>;
+}
+
+/// Random collision-resistant ids, the default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CuidGenerator;
+
+impl IdGenerator for CuidGenerator {
+  fn next_id(&mut self) -> Rc<str> {
+    Rc::from(cuid::cuid1().unwrap())
+  }
+}
+
+/// Numbers instances in creation order as `<prefix>-0`, `<prefix>-1`, ... so runs are
+/// reproducible
+#[derive(Debug, Clone)]
+pub struct SequentialIdGenerator {
+  prefix: String,
+  next: usize,
+}
+
+impl SequentialIdGenerator {
+  pub fn new(prefix: &str) -> Self {
+    SequentialIdGenerator {
+      prefix: prefix.to_string(),
+      next: 0,
+    }
+  }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+  fn next_id(&mut self) -> Rc<str> {
+    let id = format!("{}-{}", self.prefix, self.next);
+    self.next += 1;
+    Rc::from(id)
+  }
+}
@@ -1,75 +1,482 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::{Index, IndexMut};
 use std::rc::Rc;
 
+use crate::behavior::SignalReset;
+use crate::bitset::SmallBitSet;
 use crate::component::*;
-use crate::orchestrator::ExecutionContext;
+use crate::data::{Payload, Value};
+use crate::error::BurstError;
+use crate::ir::{ComponentIR, IrEdge};
+use crate::ops::{ArithmeticMode, Operation};
+use crate::orchestrator::{ExecutionContext, Observation};
+use crate::rng::SplitMix64;
 
-use petgraph::graph::NodeIndex;
+use petgraph::graph::{EdgeIndex, NodeIndex, WalkNeighbors};
+use petgraph::visit::EdgeRef;
 use petgraph::Direction;
-use tracing::trace;
+use tracing::{trace, trace_span};
 
 #[derive(Debug)]
-pub struct Instance {
+pub struct Instance<V = ()> {
   pub id: Rc<str>,
   pub node_name: String,
-  pub(crate) component: Component,
+  // index of this instance's node in the orchestrator's InstanceGraph
+  pub(crate) instance_ix: Option<NodeIndex>,
+  // shared by all instances of the component, only `nodes` is per instance. Rc rather
+  // than Arc, instances live in Rc<RefCell<..>>s and never cross threads.
+  pub(crate) component: Rc<Component<V>>,
+  pub(crate) nodes: NodeStates<V>,
   fired_nodes: Vec<NodeIndex>,
   active_nodes: Vec<NodeIndex>,
   staged_nodes: Vec<NodeIndex>,
   incoming_signals: Vec<NodeIndex>,
   instance_cycle: usize,
+  // orchestrator clock cycle during which this instance was created
+  created_at_cycle: usize,
+  // nodes carrying a signal traced by `Orchestrator::trace_signal`
+  traced_nodes: HashSet<NodeIndex>,
+  // caps how many active nodes are processed per step, the rest wait for the next step
+  max_nodes_per_step: Option<usize>,
+  // flattened component graph edges are walked through instead, see `set_use_ir`
+  ir: Option<ComponentIR>,
+  // randomness for stochastic cells, see `SplitMix64::for_instance`
+  rng: SplitMix64,
+}
+
+/// Free list of instance buffers. Workloads that create and reap many transient
+/// instances (e.g. recursive sorts) reuse allocations instead of churning them.
+#[derive(Debug, Default)]
+pub struct InstancePool<V = ()> {
+  free: Vec<InstanceBuffers>,
+  // node states cloned ahead of time by `prewarm`, by component name
+  warm: HashMap<Rc<str>, WarmStates<V>>,
+  reused: usize,
+  // instances created from the pool so far, their creation order seeds their RNG
+  created: u64,
+  // reaped pinned instances waiting to be resumed, by pinned id
+  parked: HashMap<Rc<str>, Instance<V>>,
+}
+
+/// The node weights of a single instance, indexed like the nodes of its component's graph.
+/// Cell and connector state lives in node weights, so instances of a component share its
+/// graph structure and only own a copy of these. Every kind of node carries per-instance
+/// state, the structural parts copied along (cell types, names, connector fields) are small
+/// next to it as long as connectors are few compared to cells.
+#[derive(Debug, Clone)]
+pub struct NodeStates<V = ()>(Vec<Node<V>>);
+
+impl<V: CellValue> NodeStates<V> {
+  pub fn of(component: &Component<V>) -> Self {
+    NodeStates(component.graph.node_weights().cloned().collect())
+  }
+}
+
+impl<V> NodeStates<V> {
+  pub fn node_weight(&self, node_index: NodeIndex) -> Option<&Node<V>> {
+    self.0.get(node_index.index())
+  }
+
+  pub fn node_weight_mut(&mut self, node_index: NodeIndex) -> Option<&mut Node<V>> {
+    self.0.get_mut(node_index.index())
+  }
+
+  pub fn node_indices(&self) -> impl Iterator<Item = NodeIndex> {
+    (0..self.0.len()).map(NodeIndex::new)
+  }
+
+  pub fn node_weights(&self) -> impl Iterator<Item = &Node<V>> {
+    self.0.iter()
+  }
+
+  pub fn node_weights_mut(&mut self) -> impl Iterator<Item = &mut Node<V>> {
+    self.0.iter_mut()
+  }
+
+  pub fn node_count(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Mutable references to two distinct nodes at once
+  pub fn pair_mut(&mut self, a: NodeIndex, b: NodeIndex) -> (&mut Node<V>, &mut Node<V>) {
+    assert_ne!(a, b, "pair_mut needs two distinct nodes");
+    if a < b {
+      let (low, high) = self.0.split_at_mut(b.index());
+      (&mut low[a.index()], &mut high[0])
+    } else {
+      let (low, high) = self.0.split_at_mut(a.index());
+      (&mut high[0], &mut low[b.index()])
+    }
+  }
+}
+
+impl<V> Index<NodeIndex> for NodeStates<V> {
+  type Output = Node<V>;
+
+  fn index(&self, node_index: NodeIndex) -> &Node<V> {
+    &self.0[node_index.index()]
+  }
+}
+
+impl<V> IndexMut<NodeIndex> for NodeStates<V> {
+  fn index_mut(&mut self, node_index: NodeIndex) -> &mut Node<V> {
+    &mut self.0[node_index.index()]
+  }
+}
+
+#[derive(Debug)]
+struct WarmStates<V> {
+  // the component version the states were cloned from
+  component: Rc<Component<V>>,
+  states: Vec<NodeStates<V>>,
+}
+
+#[derive(Debug, Default)]
+struct InstanceBuffers {
+  fired_nodes: Vec<NodeIndex>,
+  active_nodes: Vec<NodeIndex>,
+  staged_nodes: Vec<NodeIndex>,
+  incoming_signals: Vec<NodeIndex>,
+}
+
+impl<V: CellValue> InstancePool<V> {
+  /// Number of instances created from recycled buffers so far
+  pub fn reused(&self) -> usize {
+    self.reused
+  }
+
+  pub(crate) fn recycle(&mut self, instance: Instance<V>) {
+    let mut buffers = InstanceBuffers {
+      fired_nodes: instance.fired_nodes,
+      active_nodes: instance.active_nodes,
+      staged_nodes: instance.staged_nodes,
+      incoming_signals: instance.incoming_signals,
+    };
+    buffers.fired_nodes.clear();
+    buffers.active_nodes.clear();
+    buffers.staged_nodes.clear();
+    buffers.incoming_signals.clear();
+    self.free.push(buffers);
+  }
+
+  /// Keeps a reaped instance around so it can be resumed under its pinned id. Its child
+  /// refs are cleared since their placeholders are gone along with the instance.
+  pub(crate) fn park(&mut self, mut instance: Instance<V>) {
+    instance.instance_ix = None;
+    for node in instance.nodes.node_weights_mut() {
+      if let Node::Component(instance_ref_node) = node {
+        instance_ref_node.instance_ix = None;
+      }
+    }
+    self.parked.insert(instance.id.clone(), instance);
+  }
+
+  pub(crate) fn unpark(&mut self, instance_id: &str) -> Option<Instance<V>> {
+    self.parked.remove(instance_id)
+  }
+
+  /// Clones the node states of `count` instances of `component` ahead of time, so that
+  /// creating them later doesn't have to. States warmed for an older version of the
+  /// component are discarded.
+  pub(crate) fn prewarm(&mut self, component: &Rc<Component<V>>, count: usize) {
+    let warm = self
+      .warm
+      .entry(component.name.clone())
+      .or_insert_with(|| WarmStates {
+        component: component.clone(),
+        states: Vec::new(),
+      });
+    if !Rc::ptr_eq(&warm.component, component) {
+      warm.component = component.clone();
+      warm.states.clear();
+    }
+    let missing = count.saturating_sub(warm.states.len());
+    warm
+      .states
+      .extend((0..missing).map(|_| NodeStates::of(component)));
+  }
+
+  /// Node states warmed for `component` and not yet taken by an instance
+  pub fn warmed(&self, component: &Rc<Component<V>>) -> usize {
+    match self.warm.get(&component.name) {
+      Some(warm) if Rc::ptr_eq(&warm.component, component) => warm.states.len(),
+      _ => 0,
+    }
+  }
+
+  fn take_nodes(&mut self, component: &Rc<Component<V>>) -> NodeStates<V> {
+    match self.warm.get_mut(&component.name) {
+      Some(warm) if Rc::ptr_eq(&warm.component, component) => warm.states.pop(),
+      _ => None,
+    }
+    .unwrap_or_else(|| NodeStates::of(component))
+  }
+
+  fn take(&mut self) -> InstanceBuffers {
+    self.created += 1;
+    match self.free.pop() {
+      Some(buffers) => {
+        self.reused += 1;
+        buffers
+      }
+      None => InstanceBuffers::default(),
+    }
+  }
 }
 
 // Instance is in charge of executing it's own entire step/lifecycle with staging and active cell buffers
 // rather than have that managed by a single global executor. This helps maintain locality of cells and their operands.
 // It will also help identify boundaries for splitting processing across multiple threads.
 
-impl Instance {
-  pub fn new(node_name: String, component: &Component, init_cells: &[NodeIndex]) -> Instance {
+impl<V: CellValue> Instance<V> {
+  pub fn new(
+    node_name: String,
+    component: &Component<V>,
+    init_cells: &[NodeIndex],
+    created_at_cycle: usize,
+  ) -> Instance<V> {
+    Self::from_buffers(
+      Rc::from(cuid::cuid1().unwrap()),
+      node_name,
+      Rc::new(component.clone()),
+      NodeStates::of(component),
+      init_cells,
+      created_at_cycle,
+      InstanceBuffers::default(),
+      SplitMix64::for_instance(0, 0),
+    )
+  }
+
+  /// Like `new` with a given id, but shares `component` instead of cloning it and takes
+  /// its buffers and node states from the pool when there are any to spare. The instance's
+  /// RNG is seeded from `seed` and the pool's creation count.
+  pub fn from_pool(
+    id: Rc<str>,
+    node_name: String,
+    component: &Rc<Component<V>>,
+    init_cells: &[NodeIndex],
+    created_at_cycle: usize,
+    pool: &mut InstancePool<V>,
+    seed: u64,
+  ) -> Instance<V> {
+    let rng = SplitMix64::for_instance(seed, pool.created);
+    let nodes = pool.take_nodes(component);
+    Self::from_buffers(
+      id,
+      node_name,
+      component.clone(),
+      nodes,
+      init_cells,
+      created_at_cycle,
+      pool.take(),
+      rng,
+    )
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn from_buffers(
+    id: Rc<str>,
+    node_name: String,
+    component: Rc<Component<V>>,
+    nodes: NodeStates<V>,
+    init_cells: &[NodeIndex],
+    created_at_cycle: usize,
+    mut buffers: InstanceBuffers,
+    rng: SplitMix64,
+  ) -> Instance<V> {
     trace!("Instance::new");
+    buffers.staged_nodes.extend_from_slice(init_cells);
     Instance {
-      id: Rc::from(cuid::cuid().unwrap()),
+      id,
       node_name,
-      component: component.clone(),
-      fired_nodes: vec![],
-      active_nodes: vec![],
-      staged_nodes: init_cells.to_vec(),
-      incoming_signals: vec![],
+      instance_ix: None,
+      component,
+      nodes,
+      fired_nodes: buffers.fired_nodes,
+      active_nodes: buffers.active_nodes,
+      staged_nodes: buffers.staged_nodes,
+      incoming_signals: buffers.incoming_signals,
       instance_cycle: 0,
+      created_at_cycle,
+      traced_nodes: HashSet::new(),
+      max_nodes_per_step: None,
+      ir: None,
+      rng,
     }
   }
 
+  /// Limits the number of nodes processed in a single step so a large fan-out is
+  /// spread over several cycles instead of stalling one
+  pub fn set_max_nodes_per_step(&mut self, max_nodes_per_step: Option<usize>) {
+    self.max_nodes_per_step = max_nodes_per_step;
+  }
+
+  /// Walks outgoing edges through a `ComponentIR` of the component rather than the graph
+  pub fn set_use_ir(&mut self, use_ir: bool) {
+    self.ir = use_ir.then(|| self.component.to_ir());
+  }
+
+  /// Number of steps this instance has taken
+  pub fn instance_cycle(&self) -> usize {
+    self.instance_cycle
+  }
+
+  pub fn created_at_cycle(&self) -> usize {
+    self.created_at_cycle
+  }
+
+  /// Connector signals waiting for the next step
+  pub fn pending_signals(&self) -> usize {
+    self.incoming_signals.len()
+  }
+
+  /// Cells processed by the last step
+  pub fn active_nodes(&self) -> &[NodeIndex] {
+    &self.active_nodes
+  }
+
+  /// Cells that fired in the last step. Their signals propagate in the next one.
+  pub fn fired_nodes(&self) -> &[NodeIndex] {
+    &self.fired_nodes
+  }
+
   pub fn is_active(&self) -> bool {
-    self.staged_nodes.len() > 0 || self.fired_nodes.len() > 0 || self.incoming_signals.len() > 0
+    !self.staged_nodes.is_empty()
+      || !self.fired_nodes.is_empty()
+      || !self.incoming_signals.is_empty()
+  }
+
+  /// Returns the instance to the state of a fresh one in a single pass over the graph:
+  /// cell flags, signals and firing history are cleared, pending signals dropped and the
+  /// component's init cells staged again. Cell values are kept.
+  pub fn clear_state(&mut self) {
+    for node in self.nodes.node_weights_mut() {
+      match node {
+        Node::Cell(cell) => {
+          cell.flags = CellFlags::empty();
+          cell.clear_signals();
+          cell.last_fired_cycle = None;
+          cell.selected_output = None;
+          cell.accumulated = 0;
+        }
+        Node::ConnectorIn(connector) => {
+          connector.flags = CellFlags::empty();
+          connector.field_signals.clear();
+        }
+        Node::ConnectorOut(connector) => {
+          connector.distribution.clear();
+          connector.output = None;
+        }
+        _ => {}
+      }
+    }
+    self.fired_nodes.clear();
+    self.active_nodes.clear();
+    self.staged_nodes.clear();
+    self.incoming_signals.clear();
+    self.traced_nodes.clear();
+    self
+      .staged_nodes
+      .extend_from_slice(&self.component.init_cells);
+    self.instance_cycle = 0;
   }
 
   pub(crate) fn step(&mut self, context: &mut ExecutionContext) -> bool {
-    self.propagate_fired_signals();
+    let _span = trace_span!(
+      "instance_step",
+      instance = %self.id,
+      component = %self.component.name,
+      cycle = context.cycle()
+    )
+    .entered();
+    self.propagate_fired_signals(context);
     self.stage_signaled_and_associated_nodes(context);
-    if self.staged_nodes.len() > 0 {
+    if !self.staged_nodes.is_empty() {
       std::mem::swap(&mut self.active_nodes, &mut self.staged_nodes);
       self.staged_nodes.clear();
-      self.process_active_nodes();
+      if let Some(max_nodes_per_step) = self.max_nodes_per_step {
+        if self.active_nodes.len() > max_nodes_per_step {
+          // deferred nodes keep their STAGED flag and go first next step
+          let deferred = self.active_nodes.split_off(max_nodes_per_step);
+          self.staged_nodes.extend(deferred);
+        }
+      }
+      self.process_active_nodes(context);
+    } else {
+      self.active_nodes.clear();
     }
     self.instance_cycle += 1;
     self.is_active()
   }
 
-  fn propagate_fired_signals(&mut self) {
+  /// Checks that the STAGED and FIRED flags of the cells agree with the staged and fired
+  /// node lists, see the `debug_checks` feature
+  #[cfg(feature = "debug_checks")]
+  pub(crate) fn check_invariants(&self) -> Result<(), String> {
+    let graph = &self.component.graph;
+    let nodes = &self.nodes;
+    for ix in nodes.node_indices() {
+      if let Node::Cell(cell) = &nodes[ix] {
+        if cell.flags.contains(CellFlags::STAGED) && !self.staged_nodes.contains(&ix) {
+          return Err(format!("cell {:?} is STAGED but not staged", ix));
+        }
+        if cell.flags.contains(CellFlags::FIRED) && !self.fired_nodes.contains(&ix) {
+          return Err(format!("cell {:?} is FIRED but not in fired_nodes", ix));
+        }
+      }
+    }
+    for ix in self.fired_nodes.iter() {
+      match nodes.node_weight(*ix) {
+        Some(Node::Cell(cell)) if cell.flags.contains(CellFlags::FIRED) => {}
+        _ => return Err(format!("fired node {:?} is not a FIRED cell", ix)),
+      }
+    }
+    for edge in graph.edge_references() {
+      if let Edge::Signal(_) = edge.weight() {
+        if !matches!(nodes[edge.target()], Node::Cell(_) | Node::ConnectorOut(_)) {
+          return Err(format!(
+            "signal edge {:?} targets {:?}, which is neither a cell nor a ConnectorOut",
+            edge.id(),
+            edge.target()
+          ));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn propagate_fired_signals(&mut self, context: &mut ExecutionContext) {
     // Set connected signal flags according to connections
-    let graph = &mut self.component.graph;
+    let graph = &self.component.graph;
+    let nodes = &mut self.nodes;
     self.fired_nodes.extend_from_slice(&self.incoming_signals);
     self.incoming_signals.clear();
     for cell_index in self.fired_nodes.iter() {
-      let mut edges = graph
-        .neighbors_directed(*cell_index, Direction::Outgoing)
-        .detach();
-      while let Some((edge_index, target_index)) = edges.next(&graph) {
-        let synapse = &mut graph[edge_index];
-        if let Edge::Signal(signal) = synapse {
+      let traced = self.traced_nodes.contains(cell_index);
+      let fields = signaled_fields(nodes, *cell_index);
+      let mut edges = OutgoingEdges::new(graph, self.ir.as_ref(), *cell_index);
+      while let Some((edge_index, target_index)) = edges.next(graph) {
+        if let Edge::Signal(signal) = &graph[edge_index] {
           let bit = signal.signal_bit;
-          match &mut graph[target_index] {
+          let weight = signal.weight;
+          if fields
+            .as_ref()
+            .is_some_and(|fields| !fields.contains(bit as usize))
+          {
+            continue;
+          }
+          match &mut nodes[target_index] {
             Node::Cell(cell) => {
               cell.set_signal(bit);
+              if !cell.accumulate(weight) {
+                context.warn(BurstError::AccumulatorOverflow {
+                  instance: self.id.clone(),
+                  node: target_index,
+                });
+              }
+              if traced {
+                self.traced_nodes.insert(target_index);
+              }
             }
             _ => {
               // no other node types should have signals
@@ -82,15 +489,41 @@ impl Instance {
 
   fn stage_signaled_and_associated_nodes(&mut self, context: &mut ExecutionContext) {
     // Stage connected cells that are not already staged
-    let graph = &mut self.component.graph;
+    let sense_before_update = self.component.sense_before_update;
+    let graph = &self.component.graph;
+    let nodes = &mut self.nodes;
     for node_index in self.fired_nodes.iter() {
       trace!("staging connections of {:?}", node_index);
-      let mut edges = graph
-        .neighbors_directed(*node_index, Direction::Outgoing)
-        .detach();
-      while let Some((edge, target_index)) = edges.next(&graph) {
-        match &mut graph[edge] {
-          Edge::Signal(Signal { signal_bit: _ }) => match &mut graph[target_index] {
+      let traced = self.traced_nodes.remove(node_index);
+      if traced {
+        if let Node::ConnectorIn(_) = &nodes[*node_index] {
+          context.record_trace(self.id.clone(), *node_index);
+        }
+      }
+      if sense_before_update {
+        stage_associated_nodes(
+          graph,
+          nodes,
+          self.ir.as_ref(),
+          *node_index,
+          &mut self.staged_nodes,
+          traced.then_some(&mut self.traced_nodes),
+          context,
+        );
+      }
+      let fields = signaled_fields(nodes, *node_index);
+      let source_value = match &nodes[*node_index] {
+        Node::Cell(cell) => Some(cell.value),
+        _ => None,
+      };
+      let mut edges = OutgoingEdges::new(graph, self.ir.as_ref(), *node_index);
+      while let Some((edge, target_index)) = edges.next(graph) {
+        match &graph[edge] {
+          Edge::Signal(Signal { signal_bit, .. })
+            if fields
+              .as_ref()
+              .is_some_and(|fields| !fields.contains(*signal_bit as usize)) => {}
+          Edge::Signal(_) => match &mut nodes[target_index] {
             Node::Cell(cell) => {
               if !cell.flags.contains(CellFlags::STAGED) {
                 trace!("staging cell {:?}", target_index);
@@ -98,9 +531,39 @@ impl Instance {
                 cell.flags.insert(CellFlags::STAGED);
               }
             }
+            Node::ConnectorOut(con) if matches!(con.fan_out, FanOut::Distribute { .. }) => {
+              con.output = source_value.or(con.output);
+              if let Some(target) = con.distribute_next() {
+                context.signal_connector_with(target, con.payload.as_ref());
+                if traced {
+                  context.trace_connector(target);
+                }
+              } else if context.strict {
+                context.warn(BurstError::UnwiredConnectorOut {
+                  instance: self.id.clone(),
+                  node: target_index,
+                });
+              }
+            }
             Node::ConnectorOut(con) => {
+              con.output = source_value.or(con.output);
+              let payload = con.payload.as_ref();
+              for subscriber in con.subscribers.iter() {
+                context.signal_connector_with(*subscriber, payload);
+                if traced {
+                  context.trace_connector(*subscriber);
+                }
+              }
               if let Some(ref instance_con_ix) = con.to_instance_connector {
-                context.signal_connector(instance_con_ix.clone());
+                context.signal_connector_with(*instance_con_ix, payload);
+                if traced {
+                  context.trace_connector(*instance_con_ix);
+                }
+              } else if context.strict && con.subscribers.is_empty() {
+                context.warn(BurstError::UnwiredConnectorOut {
+                  instance: self.id.clone(),
+                  node: target_index,
+                });
               }
             }
             _ => {
@@ -110,36 +573,55 @@ impl Instance {
           Edge::Connection(_) => {
             panic!("Invalid signal receiver node {:?}", target_index);
           }
+          Edge::Observe(observe) => {
+            let observe = observe.clone();
+            if let (Some(observer_instance_ix), Node::Component(instance_ref_node)) =
+              (self.instance_ix, &nodes[observe.instance_ref])
+            {
+              if let Some(observed_instance_ix) = instance_ref_node.instance_ix {
+                context.observe(Observation {
+                  observer: InstanceComponentIx {
+                    instance_ix: observer_instance_ix,
+                    component_ix: target_index,
+                  },
+                  observed: InstanceComponentIx {
+                    instance_ix: observed_instance_ix,
+                    component_ix: observe.cell,
+                  },
+                  signal_bit: observe.signal_bit,
+                });
+              }
+            }
+          }
           _ => {}
         }
       }
 
-      if let Node::Cell(_) = &mut graph[*node_index] {
+      if !sense_before_update {
         // Associated cells (sensors) are staged separately to give explicitly signaled
         // cells a chance to modify state before doing any sensing of state changes.
-        let mut edges = graph
-          .neighbors_directed(*node_index, Direction::Outgoing)
-          .detach();
-        while let Some((edge, target_index)) = edges.next(&graph) {
-          if let Edge::Association = &graph[edge] {
-            if let Node::Cell(cell) = &mut graph[target_index] {
-              if !cell.flags.contains(CellFlags::STAGED) {
-                trace!("staging {:?}", target_index);
-                self.staged_nodes.push(target_index);
-                cell.flags.insert(CellFlags::STAGED);
-              }
-            }
-          }
-        }
-        // no other node types should be associated
+        stage_associated_nodes(
+          graph,
+          nodes,
+          self.ir.as_ref(),
+          *node_index,
+          &mut self.staged_nodes,
+          traced.then_some(&mut self.traced_nodes),
+          context,
+        );
       }
 
-      match &mut graph[*node_index] {
+      match &mut nodes[*node_index] {
         Node::Cell(cell) => {
           cell.flags.remove(CellFlags::FIRED);
+          cell.selected_output = None;
+        }
+        // asserted level-triggered connectors keep FIRED and fire again next step
+        Node::ConnectorIn(connector) if connector.flags.contains(CellFlags::FIRED) => {
+          self.incoming_signals.push(*node_index);
         }
         Node::ConnectorIn(connector) => {
-          connector.flags.remove(CellFlags::FIRED);
+          connector.field_signals.clear();
         }
         _ => {
           unimplemented!();
@@ -149,44 +631,401 @@ impl Instance {
     self.fired_nodes.clear();
   }
 
-  fn process_active_nodes(&mut self) {
-    let graph = &mut self.component.graph;
+  fn process_active_nodes(&mut self, context: &mut ExecutionContext) {
+    let nodes = &mut self.nodes;
     for node_index in self.active_nodes.iter() {
-      match &mut graph[*node_index] {
+      match &mut nodes[*node_index] {
         Node::Cell(cell) => {
           cell.flags.remove(CellFlags::STAGED);
-          match cell.cell_type {
-            CellType::Relay | CellType::OneShot => {
-              cell.flags.insert(CellFlags::FIRED);
+          cell.process(self.instance_cycle, &mut self.rng);
+          let traced = self.traced_nodes.contains(node_index);
+          if traced {
+            context.record_trace(self.id.clone(), *node_index);
+          }
+          match cell.cell_type.signal_reset() {
+            SignalReset::ClearAll => cell.clear_signals(),
+            SignalReset::ClearMatched if cell.flags.contains(CellFlags::FIRED) => {
+              cell.clear_signals()
+            }
+            SignalReset::ClearMatched | SignalReset::Retain => {}
+          }
+          if let CellType::Compute { op, operands, out } = cell.cell_type {
+            if cell.flags.contains(CellFlags::FIRED) {
+              if let Err(error) = compute(nodes, op, operands, out, context.arithmetic_mode()) {
+                context.warn(error);
+                if let Node::Cell(cell) = &mut nodes[*node_index] {
+                  cell.flags.remove(CellFlags::FIRED);
+                }
+              }
             }
           }
-          if cell.flags.contains(CellFlags::FIRED) {
+          if matches!(&nodes[*node_index], Node::Cell(cell) if cell.flags.contains(CellFlags::FIRED))
+          {
             self.fired_nodes.push(*node_index);
+          } else if traced {
+            self.traced_nodes.remove(node_index);
           }
-          // reset cell signals for next run
-          // TODO: special handling for sequence detection cells which need to hold signals across multiple cycles
-          cell.signals = 0;
         }
-        _ => {
-          unimplemented!("No other node types should be active");
+        // only cells are staged by design, a wiring bug mustn't take the run down though
+        _ => context.warn(BurstError::NonCellStaged {
+          instance: self.id.clone(),
+          node: *node_index,
+        }),
+      }
+    }
+    if !self.component.exclusive_groups.is_empty() {
+      self.enforce_exclusive_groups();
+    }
+  }
+
+  /// Unfires all but the first fired cell of each exclusive group
+  fn enforce_exclusive_groups(&mut self) {
+    let nodes = &mut self.nodes;
+    for group in self.component.exclusive_groups.iter() {
+      let mut fired = group.iter().filter(|cell_ix| {
+        matches!(&nodes[**cell_ix], Node::Cell(cell) if cell.flags.contains(CellFlags::FIRED))
+      });
+      if fired.next().is_none() {
+        continue;
+      }
+      let suppressed: Vec<NodeIndex> = fired.copied().collect();
+      for cell_ix in suppressed {
+        if let Node::Cell(cell) = &mut nodes[cell_ix] {
+          cell.flags.remove(CellFlags::FIRED);
+        }
+        self.fired_nodes.retain(|ix| *ix != cell_ix);
+        self.traced_nodes.remove(&cell_ix);
+      }
+    }
+  }
+
+  pub fn signal_connector_in(&mut self, node_index: NodeIndex) -> Result<(), BurstError> {
+    match self.nodes.node_weight_mut(node_index) {
+      Some(Node::ConnectorIn(connector)) => {
+        match connector.trigger_mode {
+          TriggerMode::Edge => self.incoming_signals.push(node_index),
+          TriggerMode::Level if !connector.flags.contains(CellFlags::FIRED) => {
+            connector.flags.insert(CellFlags::FIRED);
+            self.incoming_signals.push(node_index);
+          }
+          TriggerMode::Level => {}
         }
+        Ok(())
       }
+      _ => Err(BurstError::UnexpectedNodeType {
+        node: node_index,
+        expected: "ConnectorIn",
+      }),
     }
   }
 
-  pub fn signal_connector_in(&mut self, node_index: NodeIndex) {
-    self.incoming_signals.push(node_index);
+  /// Signals a ConnectorIn, storing `payload` on it for cells and the host to read
+  pub fn signal_connector_in_with(
+    &mut self,
+    node_index: NodeIndex,
+    payload: Payload,
+  ) -> Result<(), BurstError> {
+    self.signal_connector_in(node_index)?;
+    if let Node::ConnectorIn(connector) = &mut self.nodes[node_index] {
+      connector.payload = Some(payload);
+    }
+    Ok(())
+  }
+
+  /// The payload a ConnectorIn received last, if any
+  pub fn connector_in_payload(&self, node_index: NodeIndex) -> Option<&Payload> {
+    match self.nodes.node_weight(node_index) {
+      Some(Node::ConnectorIn(connector)) => connector.payload.as_ref(),
+      _ => None,
+    }
+  }
+
+  /// The value a ConnectorOut was last signaled with by a cell, if any
+  pub fn connector_out_value(&self, node_index: NodeIndex) -> Option<Value> {
+    match self.nodes.node_weight(node_index) {
+      Some(Node::ConnectorOut(connector)) => connector.output,
+      _ => None,
+    }
+  }
+
+  /// Deasserts a level-triggered ConnectorIn so it stops firing. Does nothing for
+  /// connectors that are not asserted.
+  pub fn release_connector_in(&mut self, node_index: NodeIndex) {
+    if let Some(Node::ConnectorIn(connector)) = self.nodes.node_weight_mut(node_index) {
+      if connector.flags.contains(CellFlags::FIRED) {
+        connector.flags.remove(CellFlags::FIRED);
+        connector.field_signals.clear();
+        self.incoming_signals.retain(|ix| *ix != node_index);
+      }
+    }
+  }
+
+  /// Signals a single named field of a connector (see `ConnectorInNode::with_field`), only
+  /// the connector's edges carrying that field's bit are followed. Otherwise like
+  /// `signal_connector_in`.
+  pub fn signal_connector_field(
+    &mut self,
+    node_index: NodeIndex,
+    signal_bit: u8,
+  ) -> Result<(), BurstError> {
+    match self.nodes.node_weight_mut(node_index) {
+      Some(Node::ConnectorIn(connector)) => {
+        connector.field_signals.insert(signal_bit as usize);
+      }
+      _ => {
+        return Err(BurstError::UnexpectedNodeType {
+          node: node_index,
+          expected: "ConnectorIn",
+        })
+      }
+    }
+    self.signal_connector_in(node_index)
+  }
+
+  /// Marks a signaled connector so everything it causes to fire is traced
+  pub(crate) fn trace_connector_in(&mut self, node_index: NodeIndex) {
+    self.traced_nodes.insert(node_index);
+  }
+
+  /// Swaps in a new version of the component, carrying over the state of every node that
+  /// still exists according to `mapping` (old index -> new index). Returns how many
+  /// pending or staged entries were dropped because their node no longer exists.
+  pub(crate) fn migrate(
+    &mut self,
+    component: &Rc<Component<V>>,
+    mapping: &HashMap<NodeIndex, NodeIndex>,
+  ) -> usize {
+    let mut migrated = NodeStates::of(component);
+    for (old_ix, new_ix) in mapping.iter() {
+      match (&self.nodes[*old_ix], &mut migrated[*new_ix]) {
+        (Node::Cell(old), Node::Cell(new)) => {
+          new.flags = old.flags;
+          new.value = old.value;
+          new.signals = old.signals.clone();
+          new.last_fired_cycle = old.last_fired_cycle;
+          new.accumulated = old.accumulated;
+          new.user = old.user.clone();
+        }
+        (Node::ConnectorIn(old), Node::ConnectorIn(new)) => {
+          new.flags = old.flags;
+          new.payload = old.payload.clone();
+        }
+        (Node::ConnectorOut(old), Node::ConnectorOut(new)) => {
+          new.to_instance_connector = old.to_instance_connector;
+          if new.broadcast {
+            new.subscribers = old.subscribers.clone();
+          }
+          if new.fan_out == old.fan_out {
+            new.distribution = old.distribution.clone();
+          }
+          new.output = old.output;
+        }
+        (Node::Component(old), Node::Component(new)) => {
+          new.instance_ix = old.instance_ix;
+        }
+        _ => {}
+      }
+    }
+    self.component = component.clone();
+    self.nodes = migrated;
+    if self.ir.is_some() {
+      self.ir = Some(self.component.to_ir());
+    }
+    self.traced_nodes.clear();
+
+    let mut dropped = 0;
+    for nodes in [
+      &mut self.fired_nodes,
+      &mut self.active_nodes,
+      &mut self.staged_nodes,
+      &mut self.incoming_signals,
+    ] {
+      let len = nodes.len();
+      *nodes = nodes
+        .iter()
+        .filter_map(|ix| mapping.get(ix).copied())
+        .collect();
+      dropped += len - nodes.len();
+    }
+    dropped
+  }
+
+  /// ConnectorIns signaling an accumulator that has taken some weight but not reached its
+  /// threshold yet, i.e. the inputs this instance is blocked on, by node index
+  pub fn pending_connectors(&self) -> Vec<NodeIndex> {
+    let waiting = |ix: NodeIndex| match &self.nodes[ix] {
+      Node::Cell(cell) => match cell.cell_type {
+        CellType::Accumulator { threshold, .. } => {
+          cell.accumulated != 0 && cell.accumulated < threshold
+        }
+        _ => false,
+      },
+      _ => false,
+    };
+    self
+      .nodes
+      .node_indices()
+      .filter(|ix| matches!(self.nodes[*ix], Node::ConnectorIn(_)))
+      .filter(|ix| {
+        self
+          .component
+          .graph
+          .edges_directed(*ix, Direction::Outgoing)
+          .any(|edge| matches!(edge.weight(), Edge::Signal(_)) && waiting(edge.target()))
+      })
+      .collect()
+  }
+
+  /// Whether the given cell fired during this instance's latest step
+  pub fn is_cell_fired(&self, cell_ix: NodeIndex) -> bool {
+    match &self.nodes[cell_ix] {
+      Node::Cell(cell) => cell.flags.contains(CellFlags::FIRED),
+      _ => false,
+    }
+  }
+
+  /// Sets a signal bit on a sensor cell and stages it for the next step
+  pub(crate) fn sense(&mut self, cell_ix: NodeIndex, signal_bit: u8) {
+    if let Node::Cell(cell) = &mut self.nodes[cell_ix] {
+      cell.set_signal(signal_bit);
+      if !cell.flags.contains(CellFlags::STAGED) {
+        self.staged_nodes.push(cell_ix);
+        cell.flags.insert(CellFlags::STAGED);
+      }
+    }
+  }
+}
+
+/// Signal bits a fired node forwards on, None when all its edges apply. These are the
+/// fields a ConnectorIn was signaled with or the output a router selected.
+fn signaled_fields<V>(nodes: &NodeStates<V>, node_index: NodeIndex) -> Option<SmallBitSet> {
+  match &nodes[node_index] {
+    Node::ConnectorIn(connector) if !connector.field_signals.is_empty() => {
+      Some(connector.field_signals.clone())
+    }
+    Node::Cell(cell) => cell.selected_output.map(|bit| {
+      let mut fields = SmallBitSet::new();
+      fields.insert(bit as usize);
+      fields
+    }),
+    _ => None,
+  }
+}
+
+/// Walks the outgoing edges of a node like a detached petgraph walker, through the
+/// instance's IR when it has one
+enum OutgoingEdges<'a> {
+  Graph(WalkNeighbors<u32>),
+  Ir(std::slice::Iter<'a, IrEdge>),
+}
+
+impl<'a> OutgoingEdges<'a> {
+  fn new<V>(graph: &ComponentGraph<V>, ir: Option<&'a ComponentIR>, node_index: NodeIndex) -> Self {
+    match ir {
+      Some(ir) => OutgoingEdges::Ir(ir.outgoing(node_index).iter()),
+      None => OutgoingEdges::Graph(
+        graph
+          .neighbors_directed(node_index, Direction::Outgoing)
+          .detach(),
+      ),
+    }
+  }
+
+  fn next<V>(&mut self, graph: &ComponentGraph<V>) -> Option<(EdgeIndex, NodeIndex)> {
+    match self {
+      OutgoingEdges::Graph(edges) => edges.next(graph),
+      OutgoingEdges::Ir(edges) => edges.next().map(|edge| (edge.edge, edge.target)),
+    }
+  }
+}
+
+/// Runs the op of a compute cell, see `CellType::Compute`
+fn compute<V: CellValue>(
+  nodes: &mut NodeStates<V>,
+  op: Operation,
+  operands: [u16; 2],
+  out: u16,
+  mode: ArithmeticMode,
+) -> Result<(), BurstError> {
+  let index = |ix: u16| NodeIndex::new(ix.into());
+  let value = |ix: NodeIndex| match nodes.node_weight(ix) {
+    Some(Node::Cell(cell)) => Ok(cell.value),
+    _ => Err(BurstError::UnexpectedNodeType {
+      node: ix,
+      expected: "Cell",
+    }),
+  };
+  let out = index(out);
+  let mut operand0 = value(index(operands[0]))?;
+  let mut operand1 = value(index(operands[1]))?;
+  value(out)?;
+  let mut output = Value::default();
+  op.do_op_in(mode, &mut operand0, &mut operand1, Some(&mut output))?;
+  if let Node::Cell(cell) = &mut nodes[out] {
+    cell.value = if op.has_output() { output } else { operand0 };
+  }
+  Ok(())
+}
+
+/// Stages the cells associated with (sensing) a fired node and applies the transforms of
+/// their associations. `traced_nodes` is given when the fired node carries a traced signal.
+fn stage_associated_nodes<V>(
+  graph: &ComponentGraph<V>,
+  nodes: &mut NodeStates<V>,
+  ir: Option<&ComponentIR>,
+  node_index: NodeIndex,
+  staged_nodes: &mut Vec<NodeIndex>,
+  mut traced_nodes: Option<&mut HashSet<NodeIndex>>,
+  context: &mut ExecutionContext,
+) {
+  if let Node::Cell(source) = &nodes[node_index] {
+    let source_value = source.value;
+    let mut edges = OutgoingEdges::new(graph, ir, node_index);
+    while let Some((edge, target_index)) = edges.next(graph) {
+      if let Edge::Association(association) = &graph[edge] {
+        let transform = association.transform;
+        if let Some(traced_nodes) = traced_nodes.as_mut() {
+          traced_nodes.insert(target_index);
+        }
+        if let Node::Cell(cell) = &mut nodes[target_index] {
+          if let Some(transform) = transform {
+            match transform.apply(source_value, context.arithmetic_mode()) {
+              Ok(value) => cell.value = value,
+              Err(error) => context.warn(error),
+            }
+          }
+          if !cell.flags.contains(CellFlags::STAGED) {
+            trace!("staging {:?}", target_index);
+            staged_nodes.push(target_index);
+            cell.flags.insert(CellFlags::STAGED);
+          }
+        }
+      }
+    }
+    // no other node types should be associated
   }
 }
 
 #[cfg(test)]
 mod tests {
   use crate::component::*;
-  use crate::instance::Instance;
+  use crate::data::Value;
+  use crate::error::BurstError;
+  use crate::instance::{Instance, InstancePool};
+  use crate::ops::Operation;
   use crate::orchestrator::ExecutionContext;
 
+  use petgraph::graph::NodeIndex;
+  use std::rc::Rc;
   use tracing_test::traced_test;
 
+  fn last_fired_cycle(instance: &Instance, cell_ix: NodeIndex) -> Option<usize> {
+    match &instance.nodes[cell_ix] {
+      Node::Cell(cell) => cell.last_fired_cycle,
+      _ => panic!("not a cell"),
+    }
+  }
+
   #[traced_test]
   #[test]
   fn it_works() {
@@ -198,19 +1037,748 @@ mod tests {
     let cell_d = component.graph.add_node(Node::Cell(CellNode::relay()));
     component
       .graph
-      .add_edge(cell_a, cell_b, Edge::Signal(Signal { signal_bit: 0 }));
-    component.graph.add_edge(cell_b, cell_c, Edge::Association);
+      .add_edge(cell_a, cell_b, Edge::new_signal(0));
     component
       .graph
-      .add_edge(cell_b, cell_d, Edge::Signal(Signal { signal_bit: 0 }));
+      .add_edge(cell_b, cell_c, Edge::new_association());
+    component
+      .graph
+      .add_edge(cell_b, cell_d, Edge::new_signal(0));
     let init_cells = [cell_a];
 
-    let mut instance = Instance::new("root_node".to_string(), &component, &init_cells);
+    let mut instance = Instance::new("root_node".to_string(), &component, &init_cells, 0);
+
+    let mut context = ExecutionContext::new();
+
+    while instance.step(&mut context) {}
+
+    assert_eq!(instance.instance_cycle, 4);
+  }
+
+  #[traced_test]
+  #[test]
+  fn relay_chain_runs_to_the_end() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let chain = component.chain_from(connector_in, &vec![CellNode::relay(); 5], 0);
+    assert_eq!(chain.len(), 5);
+    assert_eq!(component.graph.edge_count(), 5);
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    let mut context = ExecutionContext::new();
+    instance.signal_connector_in(connector_in).unwrap();
+    let mut fired: Vec<NodeIndex> = vec![];
+    while instance.step(&mut context) {
+      fired.extend(chain.iter().filter(|ix| instance.is_cell_fired(**ix)));
+    }
+
+    assert_eq!(fired, chain);
+  }
+
+  /// Signal bits are stored in a `SmallBitSet`, so every bit an edge can name reaches its
+  /// target and edges need no bounds check
+  #[test]
+  fn signal_bits_beyond_the_inline_word_reach_the_target() {
+    for bit in [40, 64, u8::MAX] {
+      let mut component = Component::new("AComponent");
+      let source = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+      let router = component.graph.add_node(Node::Cell(CellNode::router()));
+      component
+        .graph
+        .add_edge(source, router, Edge::new_signal(bit));
+
+      let mut instance = Instance::new("root_node".to_string(), &component, &[source], 0);
+      let mut context = ExecutionContext::new();
+      instance.step(&mut context);
+      instance.step(&mut context);
+      match &instance.nodes[router] {
+        Node::Cell(cell) => assert_eq!(cell.selected_output, Some(bit)),
+        _ => unreachable!(),
+      }
+    }
+  }
+
+  #[test]
+  fn init_triggers_fire_in_insertion_order() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let signaled = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(connector_in, signaled, Edge::new_signal(0));
+    let first = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+    let second = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+    let relay = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .add_init_trigger(second)
+      .unwrap()
+      .add_init_trigger(first)
+      .unwrap();
+    assert!(component.add_init_trigger(relay).is_err());
+
+    let mut instance = Instance::new(
+      "root_node".to_string(),
+      &component,
+      &component.init_cells,
+      0,
+    );
+    instance.signal_connector_in(connector_in).unwrap();
+    instance.step(&mut ExecutionContext::new());
+    assert_eq!(instance.fired_nodes(), [second, first, signaled]);
+  }
+
+  #[test]
+  fn pending_connectors_feed_unsatisfied_accumulators() {
+    let mut component = Component::new("AComponent");
+    let mut connector = |name: &str| {
+      component
+        .graph
+        .add_node(Node::ConnectorIn(ConnectorInNode::new(name.to_string())))
+    };
+    let (a, b, other) = (connector("a"), connector("b"), connector("other"));
+    let accumulator = component.graph.add_node(Node::Cell(CellNode::accumulator(
+      2,
+      OverflowPolicy::Saturate,
+    )));
+    let relay = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(a, accumulator, Edge::new_signal(0));
+    component
+      .graph
+      .add_edge(b, accumulator, Edge::new_signal(1));
+    component.graph.add_edge(other, relay, Edge::new_signal(0));
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    let mut context = ExecutionContext::new();
+    assert!(instance.pending_connectors().is_empty());
+    instance.signal_connector_in(a).unwrap();
+    instance.signal_connector_in(other).unwrap();
+    while instance.step(&mut context) {}
+    assert_eq!(instance.pending_connectors(), [a, b]);
+
+    instance.signal_connector_in(b).unwrap();
+    while instance.step(&mut context) {}
+    assert!(instance.pending_connectors().is_empty());
+  }
+
+  #[test]
+  fn staged_instance_ref_is_reported() {
+    let mut component = Component::new("AComponent");
+    let cell = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+    let instance_ref = component
+      .graph
+      .add_node(Node::Component(InstanceRefNode::new(
+        "child".to_string(),
+        Rc::from("Child"),
+      )));
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[cell], 0);
+    instance.staged_nodes.push(instance_ref);
+    let mut context = ExecutionContext::new();
+    instance.step(&mut context);
+    assert!(instance.is_cell_fired(cell));
+    assert_eq!(
+      context.warnings,
+      [BurstError::NonCellStaged {
+        instance: instance.id.clone(),
+        node: instance_ref,
+      }]
+    );
+  }
+
+  #[test]
+  fn accumulators_keep_signals_until_they_fire() {
+    let mut component = Component::new("AComponent");
+    let mut connector = |name: &str| {
+      component
+        .graph
+        .add_node(Node::ConnectorIn(ConnectorInNode::new(name.to_string())))
+    };
+    let (a, b) = (connector("a"), connector("b"));
+    let accumulator = component.graph.add_node(Node::Cell(CellNode::accumulator(
+      2,
+      OverflowPolicy::Saturate,
+    )));
+    let relay = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(a, accumulator, Edge::new_signal(0));
+    component
+      .graph
+      .add_edge(b, accumulator, Edge::new_signal(1));
+    component.graph.add_edge(a, relay, Edge::new_signal(0));
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    let mut context = ExecutionContext::new();
+    let signals = |instance: &Instance, ix| match &instance.nodes[ix] {
+      Node::Cell(cell) => (0..2)
+        .filter(|bit| cell.get_signal(*bit))
+        .collect::<Vec<_>>(),
+      _ => unreachable!(),
+    };
+    instance.signal_connector_in(a).unwrap();
+    while instance.step(&mut context) {}
+    assert!(signals(&instance, relay).is_empty());
+    assert_eq!(signals(&instance, accumulator), [0]);
+
+    instance.signal_connector_in(b).unwrap();
+    instance.step(&mut context);
+    assert!(instance.is_cell_fired(accumulator));
+    assert!(signals(&instance, accumulator).is_empty());
+  }
+
+  #[test]
+  fn latches_keep_signals_across_cycles() {
+    let mut component = Component::new("AComponent");
+    let mut connector = |name: &str| {
+      component
+        .graph
+        .add_node(Node::ConnectorIn(ConnectorInNode::new(name.to_string())))
+    };
+    let (a, b) = (connector("a"), connector("b"));
+    let latch = component.graph.add_node(Node::Cell(CellNode::latch(2)));
+    let relay = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component.graph.add_edge(a, latch, Edge::new_signal(0));
+    component.graph.add_edge(b, latch, Edge::new_signal(1));
+    component.graph.add_edge(a, relay, Edge::new_signal(0));
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    let mut context = ExecutionContext::new();
+    let signals = |instance: &Instance, ix| match &instance.nodes[ix] {
+      Node::Cell(cell) => (0..2)
+        .filter(|bit| cell.get_signal(*bit))
+        .collect::<Vec<_>>(),
+      _ => unreachable!(),
+    };
+    instance.signal_connector_in(a).unwrap();
+    for _ in 0..4 {
+      instance.step(&mut context);
+    }
+    assert!(signals(&instance, relay).is_empty());
+    assert_eq!(signals(&instance, latch), [0]);
+    assert!(!instance.is_cell_fired(latch));
+
+    instance.signal_connector_in(b).unwrap();
+    instance.step(&mut context);
+    assert!(instance.is_cell_fired(latch));
+    assert_eq!(signals(&instance, latch), [0, 1]);
+  }
+
+  #[test]
+  fn association_transforms_source_value() {
+    let mut component = Component::new("AComponent");
+    let mut source = CellNode::one_shot();
+    source.value = Value::from_i32(41);
+    let source = component.graph.add_node(Node::Cell(source));
+    let incremented = component.graph.add_node(Node::Cell(CellNode::sensor()));
+    let doubled = component.graph.add_node(Node::Cell(CellNode::sensor()));
+    component.graph.add_edge(
+      source,
+      incremented,
+      Edge::new_transform(Transform::new(
+        Operation::AddAssignSelfI32OtherI32,
+        Value::from_i32(1),
+      )),
+    );
+    component.graph.add_edge(
+      source,
+      doubled,
+      Edge::new_transform(Transform::new(
+        Operation::MulSelfI32OtherI32OutI32,
+        Value::from_i32(2),
+      )),
+    );
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[source], 0);
+    let mut context = ExecutionContext::new();
+    while instance.step(&mut context) {}
+    let value = |ix| match &instance.nodes[ix] {
+      Node::Cell(cell) => *cell.value.as_i32(),
+      _ => unreachable!(),
+    };
+    assert_eq!(value(incremented), 42);
+    assert_eq!(value(doubled), 82);
+    assert_eq!(value(source), 41);
+  }
+
+  #[test]
+  fn compute_cell_sums_operands() {
+    let mut component = Component::new("AComponent");
+    let mut trigger = CellNode::one_shot();
+    trigger.value = Value::from_i32(5);
+    let trigger = component.graph.add_node(Node::Cell(trigger));
+    let doubled = component.graph.add_node(Node::Cell(CellNode::sensor()));
+    let mut addend = CellNode::sensor();
+    addend.value = Value::from_i32(3);
+    let addend = component.graph.add_node(Node::Cell(addend));
+    let sum = component.graph.add_node(Node::Cell(CellNode::sensor()));
+    let compute = component.graph.add_node(Node::Cell(
+      CellNode::compute(Operation::AddSelfI32OtherI32OutI32, [doubled, addend], sum).unwrap(),
+    ));
+    component.graph.add_edge(
+      trigger,
+      doubled,
+      Edge::new_transform(Transform::new(
+        Operation::MulSelfI32OtherI32OutI32,
+        Value::from_i32(2),
+      )),
+    );
+    // doubled is written when the trigger fires, the compute cell runs the cycle after
+    component
+      .graph
+      .add_edge(trigger, compute, Edge::new_signal(0));
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[trigger], 0);
+    let mut context = ExecutionContext::new();
+    while instance.step(&mut context) {}
+    match &instance.nodes[sum] {
+      Node::Cell(cell) => assert_eq!(*cell.value.as_i32(), 13),
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn pooled_instances_share_structure_but_not_state() {
+    let mut component = Component::new("AComponent");
+    let cell = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+    let component = Rc::new(component);
+    let mut pool = InstancePool::default();
+    let mut instances: Vec<_> = ["a", "b"]
+      .iter()
+      .map(|id| {
+        Instance::from_pool(
+          Rc::from(*id),
+          "root_node".to_string(),
+          &component,
+          &[cell],
+          0,
+          &mut pool,
+          0,
+        )
+      })
+      .collect();
+    assert!(Rc::ptr_eq(&instances[0].component, &instances[1].component));
+
+    instances[0].step(&mut ExecutionContext::new());
+    assert!(instances[0].is_cell_fired(cell));
+    assert!(!instances[1].is_cell_fired(cell));
+    assert!(matches!(
+      &component.graph[cell],
+      Node::Cell(cell) if cell.flags.is_empty()
+    ));
+  }
+
+  /// Run with `cargo test --release -- --ignored --nocapture 1000_instances` to compare
+  /// cloning the component for every instance against sharing it
+  #[test]
+  #[ignore]
+  fn bench_1000_instances_of_10k_cell_component() {
+    let mut component = Component::new("AComponent");
+    component.chain(&vec![CellNode::relay(); 10_000], 0);
+    let start = std::time::Instant::now();
+    let cloned: Vec<_> = (0..1000)
+      .map(|_| Instance::new("root_node".to_string(), &component, &[], 0))
+      .collect();
+    println!(
+      "1000 instances cloning the component: {:?}",
+      start.elapsed()
+    );
+    drop(cloned);
+
+    let component = Rc::new(component);
+    let mut pool = InstancePool::default();
+    let start = std::time::Instant::now();
+    let shared: Vec<_> = (0..1000)
+      .map(|_| {
+        Instance::from_pool(
+          Rc::from("id"),
+          "root_node".to_string(),
+          &component,
+          &[],
+          0,
+          &mut pool,
+          0,
+        )
+      })
+      .collect();
+    println!(
+      "1000 instances sharing the component: {:?}",
+      start.elapsed()
+    );
+    std::hint::black_box(shared);
+  }
+
+  #[test]
+  fn sense_before_update_stages_sensors_first() {
+    for sense_before_update in [false, true] {
+      let mut component = Component::new("AComponent");
+      let cell_a = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+      let updated = component.graph.add_node(Node::Cell(CellNode::relay()));
+      let sensor = component.graph.add_node(Node::Cell(CellNode::sensor()));
+      component
+        .graph
+        .add_edge(cell_a, updated, Edge::new_signal(0));
+      component
+        .graph
+        .add_edge(cell_a, sensor, Edge::new_association());
+      component
+        .mark_init_cell(cell_a)
+        .set_sense_before_update(sense_before_update);
+
+      let mut instance = Instance::new(
+        "root_node".to_string(),
+        &component,
+        &component.init_cells,
+        0,
+      );
+      let mut context = ExecutionContext::new();
+      instance.step(&mut context);
+      instance.step(&mut context);
+
+      // the sensor either runs after the update it senses or ahead of it
+      let expected = if sense_before_update {
+        vec![sensor, updated]
+      } else {
+        vec![updated, sensor]
+      };
+      assert_eq!(instance.active_nodes, expected);
+    }
+  }
+
+  #[traced_test]
+  #[test]
+  fn router_forwards_on_selected_output() {
+    let mut component = Component::new("AComponent");
+    let input = component.graph.add_node(Node::Cell(CellNode::one_shot()));
+    let router = component.graph.add_node(Node::Cell(CellNode::router()));
+    let outputs: Vec<_> = (0..3)
+      .map(|bit| {
+        let output = component
+          .graph
+          .add_node(Node::Cell(CellNode::one_shot_rearm(0)));
+        component
+          .graph
+          .add_edge(router, output, Edge::new_signal(bit));
+        output
+      })
+      .collect();
+    component.graph.add_edge(input, router, Edge::new_signal(1));
+    component.mark_init_cell(input);
+
+    let mut instance = Instance::new(
+      "root_node".to_string(),
+      &component,
+      &component.init_cells,
+      0,
+    );
+    let mut context = ExecutionContext::new();
+    while instance.step(&mut context) {}
+
+    let fired: Vec<_> = outputs
+      .iter()
+      .map(|output| last_fired_cycle(&instance, *output).is_some())
+      .collect();
+    assert_eq!(fired, [false, true, false]);
+  }
+
+  #[traced_test]
+  #[test]
+  fn one_shot_rearm_respects_cooldown() {
+    let mut component = Component::new("AComponent");
+
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let cell_a = component
+      .graph
+      .add_node(Node::Cell(CellNode::one_shot_rearm(5)));
+    let cell_b = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(connector_in, cell_a, Edge::new_signal(0));
+    component
+      .graph
+      .add_edge(cell_a, cell_b, Edge::new_signal(0));
 
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
     let mut context = ExecutionContext::new();
 
+    instance.signal_connector_in(connector_in).unwrap();
     while instance.step(&mut context) {}
+    assert_eq!(last_fired_cycle(&instance, cell_a), Some(0));
+    assert_eq!(last_fired_cycle(&instance, cell_b), None);
+    assert_eq!(instance.instance_cycle, 3);
 
+    // still cooling down, so the signal is absorbed without firing
+    instance.signal_connector_in(connector_in).unwrap();
+    while instance.step(&mut context) {}
+    assert_eq!(last_fired_cycle(&instance, cell_a), Some(0));
     assert_eq!(instance.instance_cycle, 4);
+
+    // idle until the cooldown has elapsed
+    instance.step(&mut context);
+    instance.step(&mut context);
+
+    instance.signal_connector_in(connector_in).unwrap();
+    while instance.step(&mut context) {}
+    assert_eq!(last_fired_cycle(&instance, cell_a), Some(6));
+  }
+
+  fn run_fan_out(max_nodes_per_step: Option<usize>) -> (Instance, Vec<NodeIndex>) {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let source = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(connector_in, source, Edge::new_signal(0));
+    let targets: Vec<_> = (0..100)
+      .map(|_| {
+        let target = component
+          .graph
+          .add_node(Node::Cell(CellNode::one_shot_rearm(0)));
+        component
+          .graph
+          .add_edge(source, target, Edge::new_signal(0));
+        target
+      })
+      .collect();
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    instance.set_max_nodes_per_step(max_nodes_per_step);
+    let mut context = ExecutionContext::new();
+    instance.signal_connector_in(connector_in).unwrap();
+    while instance.step(&mut context) {}
+    (instance, targets)
+  }
+
+  #[traced_test]
+  #[test]
+  fn max_nodes_per_step_spreads_fan_out() {
+    let (unbounded, targets) = run_fan_out(None);
+    let (bounded, _) = run_fan_out(Some(10));
+
+    assert_eq!(unbounded.instance_cycle, 3);
+    assert!(bounded.instance_cycle > unbounded.instance_cycle);
+    for target in targets {
+      assert!(last_fired_cycle(&bounded, target).is_some());
+      assert!(last_fired_cycle(&unbounded, target).is_some());
+    }
+    for ix in bounded.nodes.node_indices() {
+      if let (Node::Cell(bounded_cell), Node::Cell(unbounded_cell)) =
+        (&bounded.nodes[ix], &unbounded.nodes[ix])
+      {
+        assert_eq!(bounded_cell.flags, unbounded_cell.flags);
+        assert_eq!(bounded_cell.signals, unbounded_cell.signals);
+      }
+    }
+  }
+
+  /// An instance of a 1000 relay chain with a sensor on every 100th relay, and the chain's
+  /// ConnectorIn
+  fn chain_instance(use_ir: bool) -> (Instance, NodeIndex) {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let chain = component.chain_from(connector_in, &vec![CellNode::relay(); 1000], 0);
+    for ix in chain.iter().step_by(100) {
+      let sensor = component.graph.add_node(Node::Cell(CellNode::sensor()));
+      component
+        .graph
+        .add_edge(*ix, sensor, Edge::new_association());
+    }
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    instance.set_use_ir(use_ir);
+    (instance, connector_in)
+  }
+
+  /// Signals the ConnectorIn and steps to quiescence, returning the nodes fired by each step
+  fn run_to_quiescence(instance: &mut Instance, connector_in: NodeIndex) -> Vec<Vec<NodeIndex>> {
+    let mut context = ExecutionContext::new();
+    instance.signal_connector_in(connector_in).unwrap();
+    let mut fired = vec![];
+    while instance.step(&mut context) {
+      fired.push(instance.fired_nodes().to_vec());
+    }
+    fired
+  }
+
+  /// Steps the instance `steps` times, returning in which steps `node` fired
+  fn fired_in_steps(instance: &mut Instance, node: NodeIndex, steps: usize) -> Vec<bool> {
+    let mut context = ExecutionContext::new();
+    (0..steps)
+      .map(|_| {
+        instance.step(&mut context);
+        instance.fired_nodes().contains(&node)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn connector_trigger_modes() {
+    let connector_instance = |connector: ConnectorInNode| {
+      let mut component = Component::new("AComponent");
+      let connector_in = component.graph.add_node(Node::ConnectorIn(connector));
+      let relay = component.chain_from(connector_in, &[CellNode::relay()], 0)[0];
+      (
+        Instance::new("root_node".to_string(), &component, &[], 0),
+        connector_in,
+        relay,
+      )
+    };
+
+    let (mut edge, connector_in, relay) =
+      connector_instance(ConnectorInNode::new("connector_in".to_string()));
+    edge.signal_connector_in(connector_in).unwrap();
+    assert_eq!(
+      fired_in_steps(&mut edge, relay, 4),
+      [true, false, false, false]
+    );
+
+    let (mut level, connector_in, relay) =
+      connector_instance(ConnectorInNode::new("connector_in".to_string()).level_triggered());
+    level.signal_connector_in(connector_in).unwrap();
+    level.signal_connector_in(connector_in).unwrap();
+    assert_eq!(fired_in_steps(&mut level, relay, 4), [true; 4]);
+    level.release_connector_in(connector_in);
+    assert_eq!(fired_in_steps(&mut level, relay, 2), [false, false]);
+    assert!(!level.is_active());
+  }
+
+  #[test]
+  fn connector_fields_follow_trigger_mode() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component.graph.add_node(Node::ConnectorIn(
+      ConnectorInNode::new("connector_in".to_string())
+        .with_field("start", 0)
+        .with_field("stop", 1)
+        .level_triggered(),
+    ));
+    let start = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let stop = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(connector_in, start, Edge::new_signal(0));
+    component
+      .graph
+      .add_edge(connector_in, stop, Edge::new_signal(1));
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    assert_eq!(
+      instance.signal_connector_field(start, 0),
+      Err(BurstError::UnexpectedNodeType {
+        node: start,
+        expected: "ConnectorIn",
+      })
+    );
+    instance.signal_connector_field(connector_in, 0).unwrap();
+    instance.signal_connector_field(connector_in, 0).unwrap();
+    let mut context = ExecutionContext::new();
+    for _ in 0..3 {
+      instance.step(&mut context);
+      assert_eq!(instance.fired_nodes(), &[start]);
+    }
+    instance.release_connector_in(connector_in);
+    assert_eq!(fired_in_steps(&mut instance, start, 2), [false, false]);
+    assert!(!instance.is_active());
+  }
+
+  #[test]
+  fn exclusive_group_fires_one_cell() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let cells: Vec<_> = (0..3)
+      .map(|_| component.graph.add_node(Node::Cell(CellNode::relay())))
+      .collect();
+    component.add_signal(connector_in, cells[1]);
+    component.add_signal(connector_in, cells[2]);
+    component.add_exclusive_group(&cells);
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    let mut context = ExecutionContext::new();
+    instance.signal_connector_in(connector_in).unwrap();
+    instance.step(&mut context);
+    assert_eq!(instance.fired_nodes(), &[cells[1]]);
+    assert!(!instance.is_cell_fired(cells[2]));
+  }
+
+  #[test]
+  fn cleared_instance_runs_like_a_fresh_one() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    component.chain_from(
+      connector_in,
+      &[CellNode::one_shot_rearm(100), CellNode::relay()],
+      0,
+    );
+    let fresh = run_to_quiescence(
+      &mut Instance::new("root_node".to_string(), &component, &[], 0),
+      connector_in,
+    );
+
+    let mut instance = Instance::new("root_node".to_string(), &component, &[], 0);
+    run_to_quiescence(&mut instance, connector_in);
+    // the one shot is still cooling down
+    assert_ne!(run_to_quiescence(&mut instance, connector_in), fresh);
+
+    instance.signal_connector_in(connector_in).unwrap();
+    instance.clear_state();
+    assert!(!instance.is_active());
+    assert_eq!(instance.instance_cycle(), 0);
+    assert_eq!(run_to_quiescence(&mut instance, connector_in), fresh);
+  }
+
+  #[test]
+  fn ir_and_graph_stepping_agree() {
+    let (mut through_graph, connector_in) = chain_instance(false);
+    let (mut through_ir, _) = chain_instance(true);
+    let fired = run_to_quiescence(&mut through_graph, connector_in);
+    assert_eq!(fired.len(), 1000);
+    assert_eq!(run_to_quiescence(&mut through_ir, connector_in), fired);
+  }
+
+  /// Run with `cargo test --release -- --ignored --nocapture ir_vs_graph` to compare
+  #[test]
+  #[ignore]
+  fn bench_ir_vs_graph_stepping() {
+    for use_ir in [false, true] {
+      let mut elapsed = std::time::Duration::ZERO;
+      for _ in 0..100 {
+        let (mut instance, connector_in) = chain_instance(use_ir);
+        let start = std::time::Instant::now();
+        std::hint::black_box(run_to_quiescence(&mut instance, connector_in));
+        elapsed += start.elapsed();
+      }
+      println!(
+        "stepping a 1000 cell chain (use_ir: {}): {:?}",
+        use_ir,
+        elapsed / 100
+      );
+    }
   }
 }
@@ -0,0 +1,173 @@
+use std::rc::Rc;
+
+use petgraph::Direction;
+
+use crate::component::{CellValue, Component};
+use crate::error::BurstError;
+
+/// Type of the values carried by a port
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValueType {
+  U8,
+  U16,
+  U32,
+  U64,
+  I8,
+  I16,
+  I32,
+  I64,
+  F32,
+  F64,
+  Array(Box<ValueType>),
+}
+
+/// A named connector of an interface. Incoming ports are implemented by a ConnectorIn,
+/// outgoing ports by a ConnectorOut of the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Port {
+  pub name: String,
+  pub direction: Direction,
+  // None for ports that only signal, like `done: <-`
+  pub value_type: Option<ValueType>,
+}
+
+impl Port {
+  pub fn new(name: &str, direction: Direction, value_type: Option<ValueType>) -> Self {
+    Port {
+      name: name.to_string(),
+      direction,
+      value_type,
+    }
+  }
+
+  /// Whether a signal leaving through `self` may enter through `other`
+  pub fn connects_to(&self, other: &Port) -> bool {
+    self.direction == Direction::Outgoing
+      && other.direction == Direction::Incoming
+      && self.value_type == other.value_type
+  }
+}
+
+/// The connector contract of a component, e.g. the DSL's
+/// `interface array_mutator { start: -> mut values: [u32]; done: <- }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+  pub name: Rc<str>,
+  pub ports: Vec<Port>,
+}
+
+impl Interface {
+  pub fn new(name: &str) -> Self {
+    Interface {
+      name: Rc::from(name),
+      ports: Vec::new(),
+    }
+  }
+
+  pub fn with_port(mut self, port: Port) -> Self {
+    self.ports.push(port);
+    self
+  }
+
+  pub fn port(&self, name: &str) -> Option<&Port> {
+    self.ports.iter().find(|port| port.name == name)
+  }
+
+  /// Checks that the component declares this interface and has a connector of the right
+  /// direction for every port
+  pub fn check<V: CellValue>(&self, component: &Component<V>) -> Result<(), BurstError> {
+    if !component.interfaces.contains(&self.name) {
+      return Err(BurstError::InterfaceNotImplemented {
+        component: component.name.clone(),
+        interface: self.name.clone(),
+        port: None,
+      });
+    }
+    let connectors = component.connectors();
+    for port in self.ports.iter() {
+      if !connectors
+        .iter()
+        .any(|connector| connector.name == port.name && connector.direction == port.direction)
+      {
+        return Err(BurstError::InterfaceNotImplemented {
+          component: component.name.clone(),
+          interface: self.name.clone(),
+          port: Some(port.name.clone()),
+        });
+      }
+    }
+    Ok(())
+  }
+
+  /// Checks a connection into `target` through this interface's port `port_name`
+  pub fn check_connection<V: CellValue>(
+    &self,
+    target: &Component<V>,
+    port_name: &str,
+  ) -> Result<(), BurstError> {
+    self.check(target)?;
+    match self.port(port_name) {
+      Some(port) if port.direction == Direction::Incoming => Ok(()),
+      _ => Err(BurstError::InterfaceNotImplemented {
+        component: target.name.clone(),
+        interface: self.name.clone(),
+        port: Some(port_name.to_string()),
+      }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::component::*;
+
+  fn array_mutator() -> Interface {
+    Interface::new("array_mutator")
+      .with_port(Port::new(
+        "start",
+        Direction::Incoming,
+        Some(ValueType::Array(Box::new(ValueType::U32))),
+      ))
+      .with_port(Port::new("done", Direction::Outgoing, None))
+  }
+
+  #[test]
+  fn ports_connect_out_to_in_of_same_type() {
+    let interface = array_mutator();
+    let start = interface.port("start").unwrap();
+    let caller_start = Port::new("start", Direction::Outgoing, start.value_type.clone());
+    assert!(caller_start.connects_to(start));
+    assert!(!start.connects_to(&caller_start));
+    assert!(!Port::new("start", Direction::Outgoing, Some(ValueType::U32)).connects_to(start));
+  }
+
+  #[test]
+  fn check_validates_connectors() {
+    let interface = array_mutator();
+    let mut sorter = Component::new("Sorter");
+    sorter
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new("start".to_string())));
+    assert!(interface.check(&sorter).is_err());
+
+    sorter.implements("array_mutator");
+    assert_eq!(
+      interface.check(&sorter),
+      Err(BurstError::InterfaceNotImplemented {
+        component: Rc::from("Sorter"),
+        interface: Rc::from("array_mutator"),
+        port: Some("done".to_string()),
+      })
+    );
+
+    sorter
+      .graph
+      .add_node(Node::ConnectorOut(ConnectorOutNode::new(
+        "done".to_string(),
+      )));
+    assert_eq!(interface.check(&sorter), Ok(()));
+    assert_eq!(interface.check_connection(&sorter, "start"), Ok(()));
+    assert!(interface.check_connection(&sorter, "done").is_err());
+  }
+}
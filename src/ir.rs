@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+
+use crate::component::*;
+
+/// What a node of a `ComponentIR` is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IrNode {
+  Cell(CellType),
+  ConnectorIn,
+  ConnectorOut,
+  Component,
+}
+
+/// An outgoing edge. `edge` indexes the edge weights of the component graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IrEdge {
+  pub edge: EdgeIndex,
+  pub target: NodeIndex,
+}
+
+/// A component graph flattened into arrays, the shape an interpreter wants for cache
+/// friendly iteration. Nodes keep their graph indices and each node's outgoing edges are
+/// listed in the order petgraph walks them, so stepping through the IR and through the
+/// graph behaves the same.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentIR {
+  pub nodes: Vec<IrNode>,
+  // the outgoing edges of node n are edges[edge_offsets[n]..edge_offsets[n + 1]]
+  pub edge_offsets: Vec<usize>,
+  pub edges: Vec<IrEdge>,
+  pub connectors_in: HashMap<String, NodeIndex>,
+  pub connectors_out: HashMap<String, NodeIndex>,
+}
+
+impl ComponentIR {
+  pub fn outgoing(&self, node_index: NodeIndex) -> &[IrEdge] {
+    let ix = node_index.index();
+    &self.edges[self.edge_offsets[ix]..self.edge_offsets[ix + 1]]
+  }
+}
+
+impl<V: CellValue> Component<V> {
+  /// Linearizes the graph, see `ComponentIR`
+  pub fn to_ir(&self) -> ComponentIR {
+    let mut ir = ComponentIR {
+      nodes: Vec::with_capacity(self.graph.node_count()),
+      edge_offsets: Vec::with_capacity(self.graph.node_count() + 1),
+      edges: Vec::with_capacity(self.graph.edge_count()),
+      connectors_in: HashMap::new(),
+      connectors_out: HashMap::new(),
+    };
+    for node_index in self.graph.node_indices() {
+      ir.nodes.push(match &self.graph[node_index] {
+        Node::Cell(cell) => IrNode::Cell(cell.cell_type),
+        Node::ConnectorIn(connector) => {
+          ir.connectors_in
+            .insert(connector.node_name.clone(), node_index);
+          IrNode::ConnectorIn
+        }
+        Node::ConnectorOut(connector) => {
+          ir.connectors_out
+            .insert(connector.node_name.clone(), node_index);
+          IrNode::ConnectorOut
+        }
+        Node::Component(_) => IrNode::Component,
+      });
+      ir.edge_offsets.push(ir.edges.len());
+      ir.edges.extend(
+        self
+          .graph
+          .edges_directed(node_index, Direction::Outgoing)
+          .map(|edge| IrEdge {
+            edge: edge.id(),
+            target: edge.target(),
+          }),
+      );
+    }
+    ir.edge_offsets.push(ir.edges.len());
+    ir
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn to_ir_keeps_walk_order() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let cells = component.chain_from(connector_in, &[CellNode::relay(), CellNode::sensor()], 0);
+    let extra = component.graph.add_node(Node::Cell(CellNode::relay()));
+    component
+      .graph
+      .add_edge(connector_in, extra, Edge::new_signal(1));
+
+    let ir = component.to_ir();
+    assert_eq!(ir.nodes[cells[1].index()], IrNode::Cell(CellType::Sensor));
+    assert_eq!(ir.connectors_in["connector_in"], connector_in);
+    for node_index in component.graph.node_indices() {
+      let walked: Vec<_> = component
+        .graph
+        .neighbors_directed(node_index, Direction::Outgoing)
+        .collect();
+      let flattened: Vec<_> = ir
+        .outgoing(node_index)
+        .iter()
+        .map(|edge| edge.target)
+        .collect();
+      assert_eq!(flattened, walked);
+    }
+  }
+}
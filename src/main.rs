@@ -1,16 +1,31 @@
 #![allow(dead_code)]
-#![recursion_limit = "512"]
+#![recursion_limit = "1024"]
 
 #[macro_use]
 extern crate lalrpop_util;
-lalrpop_mod!(pub grammar); // synthesized by LALRPOP
+lalrpop_mod!(#[allow(clippy::all)] pub grammar); // synthesized by LALRPOP
 
+mod behavior;
+mod bitset;
 mod component;
+mod data;
+mod diff;
+mod error;
+mod eval;
+mod frame;
+mod id_generator;
 mod instance;
+mod interface;
+mod ir;
+mod metrics;
+mod ops;
 mod orchestrator;
-// mod data;
-// mod ops;
 mod parser;
+mod rng;
+mod scheduler;
+mod step_observer;
+mod testing;
+mod topology;
 
 fn main() {
   /*  let mut val = crate::data::Value {
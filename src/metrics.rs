@@ -0,0 +1,29 @@
+/// Counters collected by an orchestrator while it steps (see `Orchestrator::metrics`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionMetrics {
+  // clock cycles stepped. Merged runs are assumed to have run side by side, so this is
+  // the longest of them.
+  pub cycles: usize,
+  // clock cycles stepped by all runs together
+  pub total_cycles: usize,
+  pub instance_steps: usize,
+  pub cells_processed: usize,
+  pub cells_fired: usize,
+}
+
+impl ExecutionMetrics {
+  /// Combines the metrics of independent runs into one report
+  pub fn merge(others: &[ExecutionMetrics]) -> ExecutionMetrics {
+    others
+      .iter()
+      .fold(ExecutionMetrics::default(), |merged, other| {
+        ExecutionMetrics {
+          cycles: merged.cycles.max(other.cycles),
+          total_cycles: merged.total_cycles + other.total_cycles,
+          instance_steps: merged.instance_steps + other.instance_steps,
+          cells_processed: merged.cells_processed + other.cells_processed,
+          cells_fired: merged.cells_fired + other.cells_fired,
+        }
+      })
+  }
+}
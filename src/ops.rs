@@ -1,31 +1,186 @@
 use crate::data::Value;
+use crate::error::BurstError;
 
 use arrayvec::ArrayVec;
 use paste::paste;
+use std::convert::TryFrom;
 use std::slice::from_raw_parts_mut;
 
+// Integer arithmetic that overflows according to an ArithmeticMode, `$method` is the
+// name shared by the wrapping_, checked_ and saturating_ methods
+macro_rules! arith {
+  ($self:ident, $mode:ident, $method:ident, $a:expr, $b:expr) => {
+    paste! {
+      match $mode {
+        ArithmeticMode::Wrapping => ($a).[<wrapping_ $method>]($b),
+        ArithmeticMode::Saturating => ($a).[<saturating_ $method>]($b),
+        ArithmeticMode::Checked => ($a)
+          .[<checked_ $method>]($b)
+          .ok_or(BurstError::ArithmeticOverflow { op: $self })?,
+        ArithmeticMode::Panicking => ($a)
+          .[<checked_ $method>]($b)
+          .unwrap_or_else(|| panic!("arithmetic overflow in {:?}", $self)),
+      }
+    }
+  };
+}
+
+// Integer Div/Rem according to an ArithmeticMode, `$method` is `div` or `rem`. Their only
+// overflow is MIN / -1, whose remainder is always 0, so it saturates by wrapping.
+macro_rules! divide {
+  ($self:ident, $mode:ident, div, $a:expr, $b:expr) => {
+    divide!(@ $self, $mode, div, saturating_div, $a, $b)
+  };
+  ($self:ident, $mode:ident, rem, $a:expr, $b:expr) => {
+    divide!(@ $self, $mode, rem, wrapping_rem, $a, $b)
+  };
+  (@ $self:ident, $mode:ident, $method:ident, $saturating:ident, $a:expr, $b:expr) => {
+    paste! {
+      match $mode {
+        ArithmeticMode::Wrapping => ($a).[<wrapping_ $method>]($b),
+        ArithmeticMode::Saturating => ($a).$saturating($b),
+        ArithmeticMode::Checked => ($a)
+          .[<checked_ $method>]($b)
+          .ok_or(BurstError::ArithmeticOverflow { op: $self })?,
+        ArithmeticMode::Panicking => ($a)
+          .[<checked_ $method>]($b)
+          .unwrap_or_else(|| panic!("arithmetic overflow in {:?}", $self)),
+      }
+    }
+  };
+}
+
+// Integer shifts according to an ArithmeticMode, `$method` is `shl` or `shr`. Wrapping and
+// Saturating mask the amount to the bit width of the type, Checked and Panicking treat
+// negative amounts and amounts of at least the bit width as overflow.
+macro_rules! shift {
+  ($self:ident, $mode:ident, $method:ident, $a:expr, $b:expr) => {
+    paste! {
+      match $mode {
+        ArithmeticMode::Wrapping | ArithmeticMode::Saturating => ($a).[<wrapping_ $method>]($b as u32),
+        ArithmeticMode::Checked => u32::try_from($b)
+          .ok()
+          .and_then(|amount| ($a).[<checked_ $method>](amount))
+          .ok_or(BurstError::ArithmeticOverflow { op: $self })?,
+        ArithmeticMode::Panicking => u32::try_from($b)
+          .ok()
+          .and_then(|amount| ($a).[<checked_ $method>](amount))
+          .unwrap_or_else(|| panic!("arithmetic overflow in {:?}", $self)),
+      }
+    }
+  };
+}
+
 macro_rules! define_match {
-  ($self:ident, $op0:ident, $op1:ident, $op2:ident $($func:ident($op:tt $num:ident ($($type_name:tt)+)))+) => {
-      define_match!(@ $self, $op0, $op1, $op2 {[]} $($func($op $num ($($type_name)+)))+);
+  ($self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident $($func:ident($op:tt $num:ident ($($type_name:tt)+)))+) => {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {[]} $($func($op $num ($($type_name)+)))+);
   };
-  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident {[$($match:tt)*]} $func:ident($op:tt $num:ident ($($type_name:tt)+)) $($tail:tt)*) => {
-    define_match!(@ $self, $op0, $op1, $op2 {[$($match)*]} $($func($op $num $type_name))+ $($tail)*);
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt $num:ident ($($type_name:tt)+)) $($tail:tt)*) => {
+    define_match!(@ $self, $op0, $op1, $op2, $mode {[$($match)*]} $($func($op $num $type_name))+ $($tail)*);
   };
-  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident {[$($match:tt)*]} $func:ident($op:tt two $type_name:ty) $($tail:tt)*) => {
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt two $type_name:ty) $($tail:tt)*) => {
     paste! {
-      define_match!(@ $self, $op0, $op1, $op2 {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
         [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper>] => *$op0.[<as_ $type_name _mut>]() $op *$op1.[<as_ $type_name>](),]
       } $($tail)*);
     }
   };
-  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident {[$($match:tt)*]} $func:ident($op:tt three $type_name:ty) $($tail:tt)*) => {
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt two_nonzero $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper>] => {
+          if *$op1.[<as_ $type_name>]() == 0 {
+            return Err(BurstError::DivisionByZero { op: $self });
+          }
+          *$op0.[<as_ $type_name _mut>]() = divide!($self, $mode, $op, *$op0.[<as_ $type_name>](), *$op1.[<as_ $type_name>]())
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt three_nonzero $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>] => {
+          if *$op1.[<as_ $type_name>]() == 0 {
+            return Err(BurstError::DivisionByZero { op: $self });
+          }
+          *$op2.unwrap().[<as_ $type_name _mut>]() = divide!($self, $mode, $op, *$op0.[<as_ $type_name>](), *$op1.[<as_ $type_name>]())
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt two_shift $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper>] => {
+          *$op0.[<as_ $type_name _mut>]() = shift!($self, $mode, $op, *$op0.[<as_ $type_name>](), *$op1.[<as_ $type_name>]())
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt three_shift $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>] => {
+          *$op2.unwrap().[<as_ $type_name _mut>]() = shift!($self, $mode, $op, *$op0.[<as_ $type_name>](), *$op1.[<as_ $type_name>]())
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt three_method $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>] => {
+          *$op2.unwrap().[<as_ $type_name _mut>]() = (*$op0.[<as_ $type_name>]()).$op(*$op1.[<as_ $type_name>]())
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt three_rotate $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>] => {
+          *$op2.unwrap().[<as_ $type_name _mut>]() = (*$op0.[<as_ $type_name>]()).$op((*$op1.[<as_ $type_name>]() & (<$type_name>::BITS - 1) as $type_name) as u32)
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt three_clamp $type_name:ty) $($tail:tt)*) => {
     paste! {
-      define_match!(@ $self, $op0, $op1, $op2 {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>] => {
+          let (lo, hi) = $op1.[<as_ $type_name _pair>]();
+          *$op2.unwrap().[<as_ $type_name _mut>]() = (*$op0.[<as_ $type_name>]()).max(lo).min(hi)
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt two_arith $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper>] => {
+          *$op0.[<as_ $type_name _mut>]() = arith!($self, $mode, $op, *$op0.[<as_ $type_name>](), *$op1.[<as_ $type_name>]())
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt three_arith $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
+        [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>] => {
+          *$op2.unwrap().[<as_ $type_name _mut>]() = arith!($self, $mode, $op, *$op0.[<as_ $type_name>](), *$op1.[<as_ $type_name>]())
+        }]
+      } $($tail)*);
+    }
+  };
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]} $func:ident($op:tt three $type_name:ty) $($tail:tt)*) => {
+    paste! {
+      define_match!(@ $self, $op0, $op1, $op2, $mode {
         [$($match)* Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>] => *$op2.unwrap().[<as_ $type_name _mut>]() = *$op0.[<as_ $type_name>]() $op *$op1.[<as_ $type_name>](),]
       } $($tail)*);
     }
   };
-  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident {[$($match:tt)*]}) => {
+  (@ $self:ident, $op0:ident, $op1:ident, $op2:ident, $mode:ident {[$($match:tt)*]}) => {
         match $self {
           $($match)*
         }
@@ -34,57 +189,109 @@ macro_rules! define_match {
 
 macro_rules! define_ops {
   ($($func:ident($op:tt $num:ident ($($type_name:tt)+)))+) => {
-      define_ops!(@ {[]} $($func($op $num ($($type_name)+)))+);
+      define_ops!(@ {[] []} $($func($op $num ($($type_name)+)))+);
 
       impl Operation {
-        pub fn do_op(self, operand0: &mut Value, operand1: &mut Value, operand2: Option<&mut Value>) {
+        pub fn do_op(self, operand0: &mut Value, operand1: &mut Value, operand2: Option<&mut Value>) -> Result<(), BurstError> {
+          self.do_op_in(ArithmeticMode::default(), operand0, operand1, operand2)
+        }
+
+        /// Like `do_op`, with integer overflow handled according to `mode`
+        pub fn do_op_in(self, mode: ArithmeticMode, operand0: &mut Value, operand1: &mut Value, operand2: Option<&mut Value>) -> Result<(), BurstError> {
           // variables must be passed in for hygienic purposes
-          define_match! (self, operand0, operand1, operand2
+          define_match! (self, operand0, operand1, operand2, mode
             $($func($op $num ($($type_name)+)))+
           );
+          Ok(())
         }
       }
 
     };
 
-  (@ {[$($variant:tt)*]} $func:ident($op:tt $num:ident ($($type_name:tt)+)) $($tail:tt)*) => {
-    define_ops!(@ {[$($variant)*]} $($func($op $num $type_name))+ $($tail)*);
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt $num:ident ($($type_name:tt)+)) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $($func($op $num $type_name))+ $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt two_nonzero $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op two $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt three_nonzero $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op three $type_name) $($tail)*);
   };
 
-  (@ {[$($variant:tt)*]} $func:ident($op:tt two $type_name:ty) $($tail:tt)*) => {
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt two_shift $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op two $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt three_shift $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op three $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt three_method $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op three $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt three_rotate $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op three $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt three_clamp $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op three $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt two_arith $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op two $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt three_arith $type_name:ty) $($tail:tt)*) => {
+    define_ops!(@ {[$($variant)*] [$($out)*]} $func($op three $type_name) $($tail)*);
+  };
+
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt two $type_name:ty) $($tail:tt)*) => {
     paste! {
       define_ops!(@ {
         [$($variant)* [<$func Self $type_name:upper Other $type_name:upper>],]
+        [$($out)*]
       } $($tail)*);
     }
   };
 
-  (@ {[$($variant:tt)*]} $func:ident($op:tt three $type_name:ty) $($tail:tt)*) => {
+  (@ {[$($variant:tt)*] [$($out:tt)*]} $func:ident($op:tt three $type_name:ty) $($tail:tt)*) => {
     paste! {
       define_ops!(@ {
         [$($variant)* [<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>],]
+        [$($out)* | Operation::[<$func Self $type_name:upper Other $type_name:upper Out $type_name:upper>]]
       } $($tail)*);
     }
   };
 
-  (@ {[$($variant:tt)*]}) => {
-    #[derive(Debug, Clone, Copy)]
+  (@ {[$($variant:tt)*] [$($out:tt)*]}) => {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum Operation {
       //$(println!(stringify!($variant));)*
       $($variant)*
     }
+
+    impl Operation {
+      /// Whether the operation writes its result to operand2 (the `Out` forms) rather
+      /// than to operand0 (the `Assign` forms)
+      pub fn has_output(self) -> bool {
+        matches!(self, $($out)*)
+      }
+    }
   };
 }
 
 // An example of what the folowing define_ops!(...) generates
-// #[derive(Debug, Clone, Copy)]
+// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 // pub enum Operation {
 //   AddSelfU8OtherU8OutU8,
 //   AddAssignSelfU8OtherU8,
 // }
 
 // impl Operation {
-//   pub fn do_op(self, operand0: &mut Value, operand1: &mut Value, operand2: Option<&mut Value>) {
+//   pub fn do_op(self, operand0: &mut Value, operand1: &mut Value, operand2: Option<&mut Value>) -> Result<(), BurstError> {
 //     match self {
 //       Operation::AddSelfU8OtherU8OutU8 => {
 //         *operand2.unwrap().as_u8_mut() = *operand0.as_u8() + *operand1.as_u8()
@@ -92,29 +299,59 @@ macro_rules! define_ops {
 //       Operation::AddAssignSelfU8OtherU8 => *operand0.as_u8_mut() += *operand1.as_u8(),
 //       _ => panic!(),
 //     }
+//     Ok(())
 //   }
 // }
+//
+// Integer Div/Rem use the *_nonzero forms, which return BurstError::DivisionByZero
+// instead of panicking and handle the MIN / -1 overflow according to an ArithmeticMode.
+// Float division by zero yields inf/NaN as usual.
+// Shifts use the *_shift forms, which by default mask the shift amount to the bit width
+// of the type (amount & (bits - 1)) like wrapping_shl/wrapping_shr, so u8 << 10 is u8 << 2.
+// Integer Add/Sub/Mul use the *_arith forms. Their overflow behavior, like that of the
+// *_nonzero and *_shift forms, is picked at runtime by an ArithmeticMode (see
+// Operation::do_op_in).
+// Min/Max use the *_method form, which calls the named method instead of an operator.
+// Rotl/Rotr use the *_rotate form, a method call whose rotate amount is masked like the
+// shift amount of the *_shift forms.
+// Clamp reads its (lo, hi) bounds packed into operand1 (see Value::from_i32_pair), so it
+// only exists for types of up to 32 bits. With lo > hi the result is hi.
 define_ops! (
-  Add(+ three (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  AddAssign(+= two (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
+  Add(add three_arith (u8 u16 u32 u64 i8 i16 i32 i64))
+  Add(+ three (f32 f64))
+  AddAssign(add two_arith (u8 u16 u32 u64 i8 i16 i32 i64))
+  AddAssign(+= two (f32 f64))
   BitAnd(& three (u8 u16 u32 u64 i8 i16 i32 i64))
   BitAndAssign(&= two (u8 u16 u32 u64 i8 i16 i32 i64))
   BitOr(| three (u8 u16 u32 u64 i8 i16 i32 i64))
   BitOrAssign(|= two (u8 u16 u32 u64 i8 i16 i32 i64))
   BitXor(^ three (u8 u16 u32 u64 i8 i16 i32 i64))
   BitXorAssign(^= two (u8 u16 u32 u64 i8 i16 i32 i64))
-  Div(/ three (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  DivAssign(/= two (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  Mul(* three (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  MulAssign(*= two (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  Rem(% three (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  RemAssign(%= two (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  Shl(<< three (u8 u16 u32 u64 i8 i16 i32 i64))
-  ShlAssign(<<= two (u8 u16 u32 u64 i8 i16 i32 i64))
-  Shr(>> three (u8 u16 u32 u64 i8 i16 i32 i64))
-  ShrAssign(>>= two (u8 u16 u32 u64 i8 i16 i32 i64))
-  Sub(- three (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
-  SubAssign(-= two (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
+  Clamp(clamp three_clamp (u8 u16 u32 i8 i16 i32 f32))
+  Div(div three_nonzero (u8 u16 u32 u64 i8 i16 i32 i64))
+  Div(/ three (f32 f64))
+  DivAssign(div two_nonzero (u8 u16 u32 u64 i8 i16 i32 i64))
+  DivAssign(/= two (f32 f64))
+  Max(max three_method (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
+  Min(min three_method (u8 u16 u32 u64 i8 i16 i32 i64 f32 f64))
+  Mul(mul three_arith (u8 u16 u32 u64 i8 i16 i32 i64))
+  Mul(* three (f32 f64))
+  MulAssign(mul two_arith (u8 u16 u32 u64 i8 i16 i32 i64))
+  MulAssign(*= two (f32 f64))
+  Rem(rem three_nonzero (u8 u16 u32 u64 i8 i16 i32 i64))
+  Rem(% three (f32 f64))
+  RemAssign(rem two_nonzero (u8 u16 u32 u64 i8 i16 i32 i64))
+  RemAssign(%= two (f32 f64))
+  Rotl(rotate_left three_rotate (u8 u16 u32 u64 i8 i16 i32 i64))
+  Rotr(rotate_right three_rotate (u8 u16 u32 u64 i8 i16 i32 i64))
+  Shl(shl three_shift (u8 u16 u32 u64 i8 i16 i32 i64))
+  ShlAssign(shl two_shift (u8 u16 u32 u64 i8 i16 i32 i64))
+  Shr(shr three_shift (u8 u16 u32 u64 i8 i16 i32 i64))
+  ShrAssign(shr two_shift (u8 u16 u32 u64 i8 i16 i32 i64))
+  Sub(sub three_arith (u8 u16 u32 u64 i8 i16 i32 i64))
+  Sub(- three (f32 f64))
+  SubAssign(sub two_arith (u8 u16 u32 u64 i8 i16 i32 i64))
+  SubAssign(-= two (f32 f64))
 );
 // TODO: Figure out what, if anything, to do with the following ops...
 // Neg
@@ -123,6 +360,22 @@ define_ops! (
 // IndexMut
 // RangeBounds
 
+/// How integer operations handle overflow: Add, Sub and Mul past the bounds of the type,
+/// Div and Rem of MIN by -1, and shifts by amounts outside 0..bits. Floats, bitwise ops,
+/// Min, Max, Clamp and rotations can't overflow and ignore the mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArithmeticMode {
+  /// Wrap around, the same in debug and release builds. Shift amounts are masked.
+  #[default]
+  Wrapping,
+  /// Fail the operation with `BurstError::ArithmeticOverflow`, leaving the result untouched
+  Checked,
+  /// Clamp to the bounds of the type. Shift amounts are masked like when wrapping.
+  Saturating,
+  /// Panic, for catching overflow while developing components
+  Panicking,
+}
+
 type ValueX3 = ArrayVec<Value, 3>;
 
 pub fn split_value_mut(values: &mut ValueX3) -> (&mut Value, &mut Value, &mut Value) {
@@ -143,35 +396,258 @@ mod tests {
 
   #[test]
   fn test_do_op() {
-    let mut operand0 = Value {
-      bytes: [1, 0, 0, 0, 0, 0, 0, 0],
+    let mut operand0 = Value::from_u8(1);
+    let mut operand1 = Value::from_u8(2);
+    let mut operand2 = Value::from_u8(3);
+
+    Operation::AddAssignSelfU8OtherU8
+      .do_op(&mut operand0, &mut operand1, None)
+      .unwrap();
+    assert_eq!(*operand0.as_u8(), 3);
+
+    Operation::AddSelfU8OtherU8OutU8
+      .do_op(&mut operand0, &mut operand1, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_u8(), 5);
+  }
+
+  #[test]
+  fn test_arithmetic_modes() {
+    let add = |mode: ArithmeticMode| {
+      let mut operand0 = Value::from_i8(120);
+      let mut operand1 = Value::from_i8(10);
+      let mut operand2 = Value::from_i8(0);
+      Operation::AddSelfI8OtherI8OutI8
+        .do_op_in(mode, &mut operand0, &mut operand1, Some(&mut operand2))
+        .map(|_| *operand2.as_i8())
     };
-    let mut operand1 = Value {
-      bytes: [2, 0, 0, 0, 0, 0, 0, 0],
+    assert_eq!(add(ArithmeticMode::Wrapping), Ok(-126));
+    assert_eq!(add(ArithmeticMode::Saturating), Ok(i8::MAX));
+    assert_eq!(
+      add(ArithmeticMode::Checked),
+      Err(BurstError::ArithmeticOverflow {
+        op: Operation::AddSelfI8OtherI8OutI8
+      })
+    );
+    assert!(std::panic::catch_unwind(|| add(ArithmeticMode::Panicking)).is_err());
+
+    let mut operand0 = Value::from_u16(3);
+    let mut operand1 = Value::from_u16(5);
+    Operation::SubAssignSelfU16OtherU16
+      .do_op(&mut operand0, &mut operand1, None)
+      .unwrap();
+    assert_eq!(*operand0.as_u16(), u16::MAX - 1);
+    Operation::MulAssignSelfU16OtherU16
+      .do_op_in(
+        ArithmeticMode::Saturating,
+        &mut operand0,
+        &mut operand1,
+        None,
+      )
+      .unwrap();
+    assert_eq!(*operand0.as_u16(), u16::MAX);
+  }
+
+  #[test]
+  fn test_integer_division_by_zero() {
+    let mut operand0 = Value::from_i32(7);
+    let mut operand1 = Value::default();
+    let mut operand2 = Value::from_i32(9);
+
+    assert_eq!(
+      Operation::DivSelfI32OtherI32OutI32.do_op(&mut operand0, &mut operand1, Some(&mut operand2)),
+      Err(BurstError::DivisionByZero {
+        op: Operation::DivSelfI32OtherI32OutI32
+      })
+    );
+    assert_eq!(*operand2.as_i32(), 9);
+
+    assert!(Operation::RemAssignSelfU8OtherU8
+      .do_op(&mut operand0, &mut operand1, None)
+      .is_err());
+    assert_eq!(*operand0.as_i32(), 7);
+
+    operand1 = Value::from_i32(2);
+    Operation::DivSelfI32OtherI32OutI32
+      .do_op(&mut operand0, &mut operand1, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_i32(), 3);
+  }
+
+  #[test]
+  fn test_integer_division_overflow() {
+    let div = |mode: ArithmeticMode| {
+      let mut operand0 = Value::from_i32(i32::MIN);
+      let mut operand1 = Value::from_i32(-1);
+      let mut operand2 = Value::default();
+      Operation::DivSelfI32OtherI32OutI32
+        .do_op_in(mode, &mut operand0, &mut operand1, Some(&mut operand2))
+        .map(|_| *operand2.as_i32())
     };
-    let mut operand2 = Value {
-      bytes: [3, 0, 0, 0, 0, 0, 0, 0],
+    assert_eq!(div(ArithmeticMode::Wrapping), Ok(i32::MIN));
+    assert_eq!(div(ArithmeticMode::Saturating), Ok(i32::MAX));
+    assert_eq!(
+      div(ArithmeticMode::Checked),
+      Err(BurstError::ArithmeticOverflow {
+        op: Operation::DivSelfI32OtherI32OutI32
+      })
+    );
+    assert!(std::panic::catch_unwind(|| div(ArithmeticMode::Panicking)).is_err());
+
+    let mut operand0 = Value::from_i8(i8::MIN);
+    let mut operand1 = Value::from_i8(-1);
+    Operation::RemAssignSelfI8OtherI8
+      .do_op(&mut operand0, &mut operand1, None)
+      .unwrap();
+    assert_eq!(*operand0.as_i8(), 0);
+    operand0 = Value::from_i64(i64::MIN);
+    operand1 = Value::from_i64(-1);
+    Operation::DivAssignSelfI64OtherI64
+      .do_op_in(
+        ArithmeticMode::Saturating,
+        &mut operand0,
+        &mut operand1,
+        None,
+      )
+      .unwrap();
+    assert_eq!(*operand0.as_i64(), i64::MAX);
+  }
+
+  #[test]
+  fn test_float_division_by_zero() {
+    let mut operand0 = Value::from_f64(1.0);
+    let mut operand1 = Value::default();
+
+    Operation::DivAssignSelfF64OtherF64
+      .do_op(&mut operand0, &mut operand1, None)
+      .unwrap();
+    assert_eq!(*operand0.as_f64(), f64::INFINITY);
+  }
+
+  #[test]
+  fn test_shift_amount_is_masked() {
+    let mut operand0 = Value::from_u8(1);
+    let mut operand1 = Value::from_u8(10);
+    let mut operand2 = Value::default();
+
+    Operation::ShlSelfU8OtherU8OutU8
+      .do_op(&mut operand0, &mut operand1, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_u8(), 1 << 2);
+
+    Operation::ShlAssignSelfU8OtherU8
+      .do_op(&mut operand0, &mut operand1, None)
+      .unwrap();
+    assert_eq!(*operand0.as_u8(), 1 << 2);
+
+    Operation::ShrAssignSelfU8OtherU8
+      .do_op(&mut operand0, &mut operand1, None)
+      .unwrap();
+    assert_eq!(*operand0.as_u8(), 1);
+
+    let mut negative = Value::from_i32(-16);
+    let mut amount = Value::from_i32(-30);
+    Operation::ShrAssignSelfI32OtherI32
+      .do_op(&mut negative, &mut amount, None)
+      .unwrap();
+    // -30 & 31 == 2
+    assert_eq!(*negative.as_i32(), -4);
+  }
+
+  #[test]
+  fn test_checked_shift() {
+    let shl = |mode: ArithmeticMode, amount: i32| {
+      let mut operand0 = Value::from_i32(1);
+      let mut operand1 = Value::from_i32(amount);
+      let mut operand2 = Value::default();
+      Operation::ShlSelfI32OtherI32OutI32
+        .do_op_in(mode, &mut operand0, &mut operand1, Some(&mut operand2))
+        .map(|_| *operand2.as_i32())
     };
+    assert_eq!(shl(ArithmeticMode::Checked, 31), Ok(i32::MIN));
+    for amount in [32, -1] {
+      assert_eq!(
+        shl(ArithmeticMode::Wrapping, amount),
+        Ok(1 << (amount & 31))
+      );
+      assert_eq!(
+        shl(ArithmeticMode::Saturating, amount),
+        Ok(1 << (amount & 31))
+      );
+      assert_eq!(
+        shl(ArithmeticMode::Checked, amount),
+        Err(BurstError::ArithmeticOverflow {
+          op: Operation::ShlSelfI32OtherI32OutI32
+        })
+      );
+      assert!(std::panic::catch_unwind(|| shl(ArithmeticMode::Panicking, amount)).is_err());
+    }
+  }
+
+  #[test]
+  fn test_rotate() {
+    let mut operand0 = Value::from_u8(0b1000_0001);
+    let mut operand1 = Value::from_u8(1);
+    let mut operand2 = Value::default();
+
+    Operation::RotlSelfU8OtherU8OutU8
+      .do_op(&mut operand0, &mut operand1, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_u8(), 0b0000_0011);
+
+    Operation::RotrSelfU8OtherU8OutU8
+      .do_op(&mut operand0, &mut operand1, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_u8(), 0b1100_0000);
 
-    Operation::AddAssignSelfU8OtherU8.do_op(&mut operand0, &mut operand1, None);
-    assert_eq!(operand0.bytes, [3, 0, 0, 0, 0, 0, 0, 0]);
+    // only the low 3 bits of the amount count for a u8, 9 & 7 == 1
+    let mut amount = Value::from_u8(9);
+    Operation::RotlSelfU8OtherU8OutU8
+      .do_op(&mut operand0, &mut amount, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_u8(), 0b0000_0011);
 
-    Operation::AddSelfU8OtherU8OutU8.do_op(&mut operand0, &mut operand1, Some(&mut operand2));
-    assert_eq!(operand2.bytes, [5, 0, 0, 0, 0, 0, 0, 0]);
+    let mut operand0 = Value::from_i32(i32::MIN);
+    let mut amount = Value::from_i32(-31);
+    Operation::RotlSelfI32OtherI32OutI32
+      .do_op(&mut operand0, &mut amount, Some(&mut operand2))
+      .unwrap();
+    // -31 & 31 == 1
+    assert_eq!(*operand2.as_i32(), 1);
+  }
+
+  #[test]
+  fn test_min_max_clamp() {
+    let mut operand0 = Value::from_i32(-3);
+    let mut operand1 = Value::from_i32(5);
+    let mut operand2 = Value::default();
+
+    Operation::MaxSelfI32OtherI32OutI32
+      .do_op(&mut operand0, &mut operand1, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_i32(), 5);
+
+    Operation::MinSelfI32OtherI32OutI32
+      .do_op(&mut operand0, &mut operand1, Some(&mut operand2))
+      .unwrap();
+    assert_eq!(*operand2.as_i32(), -3);
+
+    let mut bounds = Value::from_i32_pair(0, 10);
+    for (value, clamped) in [(-3, 0), (7, 7), (12, 10)] {
+      let mut operand0 = Value::from_i32(value);
+      Operation::ClampSelfI32OtherI32OutI32
+        .do_op(&mut operand0, &mut bounds, Some(&mut operand2))
+        .unwrap();
+      assert_eq!(*operand2.as_i32(), clamped);
+    }
   }
 
   #[test]
   fn test_split_value_mut() {
     let mut operands = ValueX3::new();
-    operands.push(Value {
-      bytes: [1, 0, 0, 0, 0, 0, 0, 0],
-    });
-    operands.push(Value {
-      bytes: [2, 0, 0, 0, 0, 0, 0, 0],
-    });
-    operands.push(Value {
-      bytes: [3, 0, 0, 0, 0, 0, 0, 0],
-    });
+    operands.push(Value::from_u8(1));
+    operands.push(Value::from_u8(2));
+    operands.push(Value::from_u8(3));
     let (op1, op2, op3) = split_value_mut(&mut operands);
     {
       *op3.as_u8_mut() = *op1.as_u8() + *op2.as_u8() * 10;
@@ -0,0 +1,32 @@
+/// Small deterministic random number generator (SplitMix64). Instances each own one so
+/// stochastic cells are reproducible for a given root seed.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+  state: u64,
+}
+
+impl SplitMix64 {
+  pub fn new(seed: u64) -> Self {
+    SplitMix64 { state: seed }
+  }
+
+  /// A generator for the `order`th instance created under `seed`. Streams of neighbouring
+  /// instances are decorrelated by scrambling the combined seed.
+  pub fn for_instance(seed: u64, order: u64) -> Self {
+    let mut seeder = SplitMix64::new(seed ^ order.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    SplitMix64::new(seeder.next_u64())
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// Uniform in [0, 1)
+  pub fn next_f32(&mut self) -> f32 {
+    (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+  }
+}
@@ -0,0 +1,20 @@
+use std::fmt::Debug;
+
+use petgraph::graph::NodeIndex;
+
+use crate::orchestrator::InstanceGraph;
+
+/// Decides the order in which the active instances of a clock cycle are stepped
+pub trait Scheduler<V = ()>: Debug {
+  fn order(&self, active: &[NodeIndex], graph: &InstanceGraph<V>) -> Vec<NodeIndex>;
+}
+
+/// Steps instances in the order they were queued
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FifoScheduler;
+
+impl<V> Scheduler<V> for FifoScheduler {
+  fn order(&self, active: &[NodeIndex], _graph: &InstanceGraph<V>) -> Vec<NodeIndex> {
+    active.to_vec()
+  }
+}
@@ -0,0 +1,11 @@
+use std::fmt::Debug;
+
+use petgraph::graph::NodeIndex;
+
+/// Hook for profilers and debuggers, called around each instance step of a clock cycle
+pub trait StepObserver: Debug {
+  fn before_step(&mut self, cycle: usize, instance_ix: NodeIndex);
+
+  /// `did_work` tells whether the instance had pending signals or staged cells
+  fn after_step(&mut self, cycle: usize, instance_ix: NodeIndex, did_work: bool);
+}
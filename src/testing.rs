@@ -0,0 +1,85 @@
+//! Drives single instances without an orchestrator, for focused tests of a component's
+//! behavior. Cross-instance effects (ConnectorOut signals, observations) are recorded in
+//! the context but never delivered.
+
+use petgraph::graph::NodeIndex;
+
+use crate::component::{CellValue, Component};
+use crate::instance::Instance;
+use crate::orchestrator::ExecutionContext;
+
+/// Creates an instance of `component` that is not part of any instance graph. `init_cells`
+/// are staged for the first step.
+pub fn isolated_instance<V: CellValue>(
+  component: &Component<V>,
+  init_cells: &[NodeIndex],
+) -> Instance<V> {
+  Instance::new("isolated".to_string(), component, init_cells, 0)
+}
+
+/// An empty context for stepping isolated instances
+pub fn isolated_context() -> ExecutionContext {
+  ExecutionContext::new()
+}
+
+/// Steps the instance once. Returns whether it has work left for another step.
+pub fn step_instance<V: CellValue>(
+  instance: &mut Instance<V>,
+  context: &mut ExecutionContext,
+) -> bool {
+  instance.step(context)
+}
+
+/// Steps the instance until it has no work left and returns the number of steps taken,
+/// giving up after `max_steps`
+pub fn run_instance<V: CellValue>(
+  instance: &mut Instance<V>,
+  context: &mut ExecutionContext,
+  max_steps: usize,
+) -> usize {
+  let mut steps = 0;
+  while steps < max_steps {
+    steps += 1;
+    if !step_instance(instance, context) {
+      break;
+    }
+  }
+  steps
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::component::*;
+
+  #[test]
+  fn signal_runs_through_isolated_instance() {
+    let mut component = Component::new("AComponent");
+    let connector_in = component
+      .graph
+      .add_node(Node::ConnectorIn(ConnectorInNode::new(
+        "connector_in".to_string(),
+      )));
+    let relay = component.graph.add_node(Node::Cell(CellNode::relay()));
+    let last = component
+      .graph
+      .add_node(Node::Cell(CellNode::one_shot_rearm(0)));
+    component
+      .graph
+      .add_edge(connector_in, relay, Edge::new_signal(0));
+    component.graph.add_edge(relay, last, Edge::new_signal(0));
+
+    let mut instance = isolated_instance(&component, &[]);
+    let mut context = isolated_context();
+    assert!(!instance.is_active());
+
+    instance.signal_connector_in(connector_in).unwrap();
+    assert!(step_instance(&mut instance, &mut context));
+    assert_eq!(run_instance(&mut instance, &mut context, 10), 2);
+    assert!(!instance.is_active());
+    assert!(matches!(
+      &instance.nodes[last],
+      Node::Cell(cell) if cell.last_fired_cycle.is_some()
+    ));
+  }
+}
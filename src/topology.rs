@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// Structure of an instance graph without any cell state.
+/// Node and connector indices are the raw `NodeIndex` values of the orchestrator's graphs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyDump {
+  pub nodes: Vec<TopologyNode>,
+  pub connections: Vec<TopologyConnection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyNode {
+  pub index: usize,
+  pub component_name: String,
+  // None for placeholder nodes that have not been instantiated yet
+  pub instance_id: Option<String>,
+}
+
+impl TopologyNode {
+  pub fn is_instantiated(&self) -> bool {
+    self.instance_id.is_some()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyConnection {
+  pub from: usize,
+  pub to: usize,
+  pub from_connector_index: usize,
+  pub to_connector_index: usize,
+}